@@ -0,0 +1,48 @@
+//! Measures the time to transfer a large series to the Python process of `Matplotlib`,
+//! compared with sending the same data as lists of decimal literals.
+//!
+//! Requires Python with matplotlib and numpy:
+//!
+//! ```sh
+//! $ cargo bench --bench transfer
+//! ```
+extern crate rustplotlib;
+
+use rustplotlib::Backend;
+use rustplotlib::backend::Matplotlib;
+use std::time::{Duration, Instant};
+
+const LEN: usize = 1_000_000;
+
+fn measure<F: FnMut(&mut Matplotlib)>(mpl: &mut Matplotlib, mut f: F) -> Duration {
+  let start = Instant::now();
+  f(mpl);
+  start.elapsed()
+}
+
+fn to_decimal_list(data: &[f64]) -> String {
+  let data: Vec<String> = data.iter().map(|x| format!("{}", x)).collect();
+  format!("[{}]", data.join(","))
+}
+
+fn main() {
+  let x: Vec<f64> = (0..LEN).map(|i| i as f64 * 1e-3).collect();
+  let y: Vec<f64> = x.iter().map(|x| x.sin()).collect();
+
+  let mut mpl = Matplotlib::new().unwrap();
+  mpl.figure(&None, &None, &None, &None).unwrap();
+
+  let binary = measure(&mut mpl, |mpl| {
+    mpl.plot(&x, &y, &None, &None, &None, &None, &None).unwrap();
+  });
+  let decimal = measure(&mut mpl, |mpl| {
+    mpl.exec(format!("plt.plot({}, {})", to_decimal_list(&x), to_decimal_list(&y)))
+      .unwrap();
+  });
+
+  println!("plot() with {} points:", LEN);
+  println!("  binary (base64): {:?}", binary);
+  println!("  decimal list   : {:?}", decimal);
+  println!("  speedup        : {:.1}x",
+           decimal.as_secs_f64() / binary.as_secs_f64());
+}
@@ -35,7 +35,7 @@ fn make_figure<'a>(x: &'a [f64], y1: &'a [f64], y2: &'a [f64]) -> Figure<'a> {
           .ylim(-1.5, 1.5)))
 }
 
-fn apply_mpl(fig: &Figure, filename: &str) -> std::io::Result<()> {
+fn apply_mpl(fig: &Figure, filename: &str) -> rustplotlib::Result<()> {
   let mut mp = backend::Matplotlib::new()?;
   mp.set_style("ggplot")?;
   fig.apply(&mut mp)?;
@@ -46,8 +46,8 @@ fn apply_mpl(fig: &Figure, filename: &str) -> std::io::Result<()> {
 }
 
 #[cfg(feature = "native")]
-fn apply_mpl_native(fig: &Figure, filename: &str) -> std::io::Result<()> {
-  let mut mp = backend::MatplotlibNative::new();
+fn apply_mpl_native(fig: &Figure, filename: &str) -> rustplotlib::Result<()> {
+  let mut mp = backend::MatplotlibNative::new()?;
   mp.set_style("dark_background")?;
   fig.apply(&mut mp)?;
   mp.tight_layout()?;
@@ -57,7 +57,7 @@ fn apply_mpl_native(fig: &Figure, filename: &str) -> std::io::Result<()> {
 }
 
 fn main() {
-  let x: Vec<f64> = (0..40).into_iter().map(|i| (i as f64) * 0.08 * PI).collect();
+  let x: Vec<f64> = (0..40).map(|i| (i as f64) * 0.08 * PI).collect();
   let y1: Vec<f64> = x.iter().map(|x| x.sin()).collect();
   let y2: Vec<f64> = x.iter().map(|x| x.cos()).collect();
 
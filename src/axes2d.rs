@@ -1,25 +1,51 @@
-use std::io;
+use std::borrow::Cow;
 use backend::Backend;
+use error::{Error, Result};
 
 /// Represents an instance of `matplotlib.axes.Axes`.
 #[derive(Debug, Default)]
 pub struct Axes2D<'a> {
   plot_data: Vec<PlotData<'a>>,
+  title: Option<String>,
+  title_loc: Option<String>,
+  title_fontsize: Option<f64>,
   xlabel: Option<String>,
   ylabel: Option<String>,
   grid: bool,
   legend: Option<String>,
   xlim: Option<(f64, f64)>,
   ylim: Option<(f64, f64)>,
+  xscale: Option<Scale>,
+  yscale: Option<Scale>,
+  colorbars: Vec<(usize, Colorbar<'a>)>,
 }
 
 impl<'a> Axes2D<'a> {
   /// add a plot data.
+  #[allow(clippy::should_implement_trait)]
   pub fn add<P: Into<PlotData<'a>>>(mut self, p: P) -> Self {
     self.plot_data.push(p.into());
     self
   }
 
+  /// set the title text of the axes.
+  pub fn title(mut self, text: &str) -> Self {
+    self.title = Some(text.to_owned());
+    self
+  }
+
+  /// set the location of title (`"center"`, `"left"` or `"right"`).
+  pub fn title_loc(mut self, loc: &str) -> Self {
+    self.title_loc = Some(loc.to_owned());
+    self
+  }
+
+  /// set the font size of title, in points.
+  pub fn title_fontsize(mut self, size: f64) -> Self {
+    self.title_fontsize = Some(size);
+    self
+  }
+
   /// set the label text of x axis.
   pub fn xlabel(mut self, text: &str) -> Self {
     self.xlabel = Some(text.to_owned());
@@ -62,9 +88,44 @@ impl<'a> Axes2D<'a> {
     self
   }
 
-  pub fn apply<B: Backend>(&self, mpl: &mut B) -> io::Result<()> {
-    for ref plot in &self.plot_data {
+  /// set the scale of x axis.
+  pub fn xscale(mut self, scale: Scale) -> Self {
+    self.xscale = Some(scale);
+    self
+  }
+
+  /// set the scale of y axis.
+  pub fn yscale(mut self, scale: Scale) -> Self {
+    self.yscale = Some(scale);
+    self
+  }
+
+  /// attach a colorbar to the plot data at `index` (in order of addition).
+  ///
+  /// the plot data must be color-mapped, i.e. an `Image`, a `Contour` or a `ContourFilled`.
+  pub fn colorbar(mut self, index: usize, colorbar: Colorbar<'a>) -> Self {
+    self.colorbars.push((index, colorbar));
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    for &(index, _) in &self.colorbars {
+      match self.plot_data.get(index) {
+        Some(plot) if plot.is_mappable() => (),
+        _ => {
+          return Err(Error::InvalidInput(format!("the plot data at {} is not color-mapped", index)))
+        }
+      }
+    }
+    for (i, plot) in self.plot_data.iter().enumerate() {
       plot.apply(mpl)?;
+      // the colorbar refers to the most recently created mappable.
+      for (_, colorbar) in self.colorbars.iter().filter(|&&(index, _)| index == i) {
+        colorbar.apply(mpl)?;
+      }
+    }
+    if let Some(ref title) = self.title {
+      mpl.title(title, &self.title_loc, &self.title_fontsize)?;
     }
     if let Some(ref xlabel) = self.xlabel {
       mpl.xlabel(xlabel)?;
@@ -82,27 +143,175 @@ impl<'a> Axes2D<'a> {
     if let Some(ref ylim) = self.ylim {
       mpl.ylim(ylim)?;
     }
+    if let Some(ref xscale) = self.xscale {
+      mpl.xscale(xscale)?;
+    }
+    if let Some(ref yscale) = self.yscale {
+      mpl.yscale(yscale)?;
+    }
     Ok(())
   }
 }
 
 
+/// Scale of an axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scale {
+  Linear,
+  /// logarithmic scale with the given base.
+  Log { base: f64 },
+  /// logarithmic scale in both directions, linear within `(-linthresh, linthresh)`.
+  SymLog { linthresh: f64 },
+  /// logistic scale, for values in `(0, 1)`.
+  Logit,
+}
+
+
 /// Plot type.
 #[derive(Debug)]
 pub enum PlotData<'a> {
   Scatter(Scatter<'a>),
   Line2D(Line2D<'a>),
   FillBetween(FillBetween<'a>),
+  Bar(Bar<'a>),
+  Hist(Hist<'a>),
+  ErrorBar(ErrorBar<'a>),
+  Image(Image<'a>),
+  Contour(Contour<'a>),
+  ContourFilled(ContourFilled<'a>),
 }
 
 impl<'a> PlotData<'a> {
-  pub fn apply<B: Backend>(&self, mpl: &mut B) -> io::Result<()> {
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
     match *self {
       PlotData::Scatter(ref s) => s.apply(mpl),
       PlotData::Line2D(ref l) => l.apply(mpl),
       PlotData::FillBetween(ref f) => f.apply(mpl),
+      PlotData::Bar(ref b) => b.apply(mpl),
+      PlotData::Hist(ref h) => h.apply(mpl),
+      PlotData::ErrorBar(ref e) => e.apply(mpl),
+      PlotData::Image(ref i) => i.apply(mpl),
+      PlotData::Contour(ref c) => c.apply(mpl),
+      PlotData::ContourFilled(ref c) => c.apply(mpl),
     }
   }
+
+  /// whether the plot is drawn with a colormap, to which a colorbar can be attached.
+  pub fn is_mappable(&self) -> bool {
+    matches!(*self,
+             PlotData::Image(_) | PlotData::Contour(_) | PlotData::ContourFilled(_))
+  }
+}
+
+
+/// Colorbar which shows the colormap of a plot.
+#[derive(Debug, Default)]
+pub struct Colorbar<'a> {
+  label: Option<String>,
+  orientation: Option<String>,
+  ticks: Option<&'a [f64]>,
+  extend: Option<String>,
+}
+
+impl<'a> Colorbar<'a> {
+  pub fn label(mut self, text: &str) -> Self {
+    self.label = Some(text.to_owned());
+    self
+  }
+
+  /// set the orientation of colorbar (`"vertical"` or `"horizontal"`).
+  pub fn orientation(mut self, orientation: &str) -> Self {
+    self.orientation = Some(orientation.to_owned());
+    self
+  }
+
+  /// set the locations of ticks.
+  pub fn ticks(mut self, ticks: &'a [f64]) -> Self {
+    self.ticks = Some(ticks);
+    self
+  }
+
+  /// set which ends have the arrows for out-of-range values
+  /// (`"neither"`, `"both"`, `"min"` or `"max"`).
+  pub fn extend(mut self, extend: &str) -> Self {
+    self.extend = Some(extend.to_owned());
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    mpl.colorbar(&self.label, &self.orientation, &self.ticks, &self.extend)?;
+    Ok(())
+  }
+}
+
+/// How the points with non-finite (NaN or infinite) coordinates are handled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NonFinite {
+  /// clip the infinite coordinates to the range of finite values in the same array, so that
+  /// the points are drawn at the edge of the data. NaN is left as it is, which leaves a gap
+  /// in the line (this is the default).
+  ///
+  /// the points are masked if the array has no finite values.
+  Clip,
+  /// replace both coordinates of the point with NaN, which leaves a gap in the line.
+  Mask,
+  /// remove the point, so that its neighbors are connected.
+  Drop,
+}
+
+/// the minimum and maximum of finite values, if any.
+fn finite_range(data: &[f64]) -> Option<(f64, f64)> {
+  data.iter()
+    .filter(|v| v.is_finite())
+    .fold(None, |range, &v| match range {
+      Some((lo, hi)) => Some((v.min(lo), v.max(hi))),
+      None => Some((v, v)),
+    })
+}
+
+/// clip an infinite value into `range`, or replace it with NaN if there is no range.
+fn clip_infinite(value: f64, range: Option<(f64, f64)>) -> f64 {
+  match range {
+    _ if !value.is_infinite() => value,
+    Some((lo, _)) if value < 0.0 => lo,
+    Some((_, hi)) => hi,
+    None => f64::NAN,
+  }
+}
+
+/// the x and y coordinates of points, which are copied only if they are modified.
+type Points<'a> = (Cow<'a, [f64]>, Cow<'a, [f64]>);
+
+fn filter_nonfinite<'a>(xdata: &'a [f64],
+                        ydata: &'a [f64],
+                        mode: Option<NonFinite>)
+                        -> Result<Points<'a>> {
+  if xdata.len() != ydata.len() {
+    return Err(Error::InvalidInput(format!("the length of ydata ({}) does not match to the \
+                                            length of xdata ({})",
+                                           ydata.len(),
+                                           xdata.len())));
+  }
+  let points = xdata.iter().zip(ydata).map(|(&x, &y)| (x, y));
+  let (x, y): (Vec<f64>, Vec<f64>) = match mode.unwrap_or(NonFinite::Clip) {
+    NonFinite::Clip => {
+      if !xdata.iter().chain(ydata).any(|v| v.is_infinite()) {
+        return Ok((Cow::Borrowed(xdata), Cow::Borrowed(ydata)));
+      }
+      let (xrange, yrange) = (finite_range(xdata), finite_range(ydata));
+      points.map(|(x, y)| (clip_infinite(x, xrange), clip_infinite(y, yrange))).unzip()
+    }
+    NonFinite::Mask => {
+      points.map(|(x, y)| if x.is_finite() && y.is_finite() {
+          (x, y)
+        } else {
+          (f64::NAN, f64::NAN)
+        })
+        .unzip()
+    }
+    NonFinite::Drop => points.filter(|&(x, y)| x.is_finite() && y.is_finite()).unzip(),
+  };
+  Ok((Cow::Owned(x), Cow::Owned(y)))
 }
 
 #[derive(Debug, Default)]
@@ -112,6 +321,7 @@ pub struct Scatter<'a> {
   label: Option<String>,
   color: Option<String>,
   marker: Option<String>,
+  nonfinite: Option<NonFinite>,
 }
 
 impl<'a> Scatter<'a> {
@@ -140,9 +350,18 @@ impl<'a> Scatter<'a> {
     self
   }
 
-  pub fn apply<B: Backend>(&self, mpl: &mut B) -> io::Result<()> {
-    mpl.scatter(self.xdata,
-               self.ydata,
+  /// set how the points with NaN or infinite coordinates are handled.
+  ///
+  /// if not set, the infinite values are clipped and NaN is left as it is (`NonFinite::Clip`).
+  pub fn nonfinite(mut self, mode: NonFinite) -> Self {
+    self.nonfinite = Some(mode);
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    let (xdata, ydata) = filter_nonfinite(self.xdata, self.ydata, self.nonfinite)?;
+    mpl.scatter(&xdata,
+               &ydata,
                &self.label,
                &self.color,
                &self.marker)?;
@@ -166,6 +385,7 @@ pub struct Line2D<'a> {
   marker: Option<String>,
   linestyle: Option<String>,
   linewidth: Option<f64>,
+  nonfinite: Option<NonFinite>,
 }
 
 impl<'a> Line2D<'a> {
@@ -204,9 +424,18 @@ impl<'a> Line2D<'a> {
     self
   }
 
-  pub fn apply<B: Backend>(&self, mpl: &mut B) -> io::Result<()> {
-    mpl.plot(self.xdata,
-            self.ydata,
+  /// set how the points with NaN or infinite coordinates are handled.
+  ///
+  /// if not set, the infinite values are clipped and NaN is left as it is (`NonFinite::Clip`).
+  pub fn nonfinite(mut self, mode: NonFinite) -> Self {
+    self.nonfinite = Some(mode);
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    let (xdata, ydata) = filter_nonfinite(self.xdata, self.ydata, self.nonfinite)?;
+    mpl.plot(&xdata,
+            &ydata,
             &self.label,
             &self.color,
             &self.marker,
@@ -231,6 +460,7 @@ pub struct FillBetween<'a> {
   where_: Option<&'a [bool]>,
   interpolate: bool,
   step: Option<String>,
+  label: Option<String>,
 }
 
 impl<'a> FillBetween<'a> {
@@ -256,13 +486,19 @@ impl<'a> FillBetween<'a> {
     self
   }
 
-  pub fn apply<B: Backend>(&self, mpl: &mut B) -> io::Result<()> {
+  pub fn label(mut self, text: &str) -> Self {
+    self.label = Some(text.to_owned());
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
     mpl.fill_between(self.x,
                     self.y1,
                     self.y2,
                     &self.where_,
                     self.interpolate,
-                    &self.step)?;
+                    &self.step,
+                    &self.label)?;
     Ok(())
   }
 }
@@ -272,3 +508,704 @@ impl<'a> From<FillBetween<'a>> for PlotData<'a> {
     PlotData::FillBetween(data)
   }
 }
+
+
+/// Vertical or horizontal bar chart.
+///
+/// Stacked bars can be drawn by adding several `Bar`s whose `bottom` is
+/// the cumulative height of the preceding ones, and grouped bars by
+/// shifting the positions of each group by a fraction of `width`.
+#[derive(Debug, Default)]
+pub struct Bar<'a> {
+  x: &'a [f64],
+  height: &'a [f64],
+  width: Option<f64>,
+  bottom: Option<&'a [f64]>,
+  horizontal: bool,
+  label: Option<String>,
+  color: Option<String>,
+  edgecolor: Option<String>,
+  align: Option<String>,
+}
+
+impl<'a> Bar<'a> {
+  pub fn new(name: &str) -> Bar<'a> {
+    Bar::default().label(name)
+  }
+
+  /// set the positions and the lengths of bars.
+  pub fn data(mut self, x: &'a [f64], height: &'a [f64]) -> Self {
+    self.x = x;
+    self.height = height;
+    self
+  }
+
+  /// set the thickness of bars.
+  pub fn width(mut self, width: f64) -> Self {
+    self.width = Some(width);
+    self
+  }
+
+  /// set the base values of bars.
+  ///
+  /// if the bars are horizontal, this is the left side of bars.
+  pub fn bottom(mut self, bottom: &'a [f64]) -> Self {
+    self.bottom = Some(bottom);
+    self
+  }
+
+  /// set whether the bars are drawn horizontally (`plt.barh()`) or not.
+  pub fn horizontal(mut self, horizontal: bool) -> Self {
+    self.horizontal = horizontal;
+    self
+  }
+
+  pub fn label(mut self, text: &str) -> Self {
+    self.label = Some(text.to_owned());
+    self
+  }
+
+  pub fn color(mut self, color: &str) -> Self {
+    self.color = Some(color.to_owned());
+    self
+  }
+
+  pub fn edgecolor(mut self, color: &str) -> Self {
+    self.edgecolor = Some(color.to_owned());
+    self
+  }
+
+  /// set the alignment of bars to their positions (`"center"` or `"edge"`).
+  pub fn align(mut self, align: &str) -> Self {
+    self.align = Some(align.to_owned());
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    mpl.bar(self.x,
+           self.height,
+           &self.width,
+           &self.bottom,
+           self.horizontal,
+           &self.label,
+           &self.color,
+           &self.edgecolor,
+           &self.align)?;
+    Ok(())
+  }
+}
+
+impl<'a> From<Bar<'a>> for PlotData<'a> {
+  fn from(data: Bar<'a>) -> PlotData<'a> {
+    PlotData::Bar(data)
+  }
+}
+
+
+/// Specification of the bins of a histogram.
+#[derive(Debug, Clone, Copy)]
+pub enum Bins<'a> {
+  /// the number of equal-width bins.
+  Count(usize),
+  /// the edges of bins, including the right edge of the last bin.
+  Edges(&'a [f64]),
+}
+
+impl<'a> From<usize> for Bins<'a> {
+  fn from(n: usize) -> Bins<'a> {
+    Bins::Count(n)
+  }
+}
+
+impl<'a> From<&'a [f64]> for Bins<'a> {
+  fn from(edges: &'a [f64]) -> Bins<'a> {
+    Bins::Edges(edges)
+  }
+}
+
+/// Histogram of one or more datasets.
+#[derive(Debug, Default)]
+pub struct Hist<'a> {
+  data: Vec<&'a [f64]>,
+  bins: Option<Bins<'a>>,
+  weights: Option<Vec<&'a [f64]>>,
+  density: bool,
+  cumulative: bool,
+  histtype: Option<String>,
+  label: Option<String>,
+  color: Option<String>,
+}
+
+impl<'a> Hist<'a> {
+  pub fn new(name: &str) -> Hist<'a> {
+    Hist::default().label(name)
+  }
+
+  /// set a dataset to be binned.
+  pub fn data(mut self, data: &'a [f64]) -> Self {
+    self.data = vec![data];
+    self
+  }
+
+  /// set multiple datasets, which are drawn side by side.
+  ///
+  /// use `histtype("barstacked")` to stack them instead.
+  pub fn datasets(mut self, data: &[&'a [f64]]) -> Self {
+    self.data = data.to_vec();
+    self
+  }
+
+  /// set the number of bins, or the edges of bins.
+  pub fn bins<B: Into<Bins<'a>>>(mut self, bins: B) -> Self {
+    self.bins = Some(bins.into());
+    self
+  }
+
+  /// set the weights of each value, one array per dataset.
+  pub fn weights(mut self, weights: &[&'a [f64]]) -> Self {
+    self.weights = Some(weights.to_vec());
+    self
+  }
+
+  /// set whether the histogram is normalized to form a probability density.
+  pub fn density(mut self, density: bool) -> Self {
+    self.density = density;
+    self
+  }
+
+  /// set whether the counts are accumulated over the bins.
+  pub fn cumulative(mut self, cumulative: bool) -> Self {
+    self.cumulative = cumulative;
+    self
+  }
+
+  /// set the type of histogram (`"bar"`, `"barstacked"`, `"step"` or `"stepfilled"`).
+  pub fn histtype(mut self, histtype: &str) -> Self {
+    self.histtype = Some(histtype.to_owned());
+    self
+  }
+
+  pub fn label(mut self, text: &str) -> Self {
+    self.label = Some(text.to_owned());
+    self
+  }
+
+  /// set the color of bars.
+  ///
+  /// only one color can be given, so this cannot be used with multiple datasets
+  /// (matplotlib rejects it). their colors are chosen from the color cycle instead.
+  pub fn color(mut self, color: &str) -> Self {
+    self.color = Some(color.to_owned());
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    mpl.hist(&self.data,
+            &self.bins,
+            &self.weights.as_ref().map(|w| &w[..]),
+            self.density,
+            self.cumulative,
+            &self.histtype,
+            &self.label,
+            &self.color)?;
+    Ok(())
+  }
+}
+
+impl<'a> From<Hist<'a>> for PlotData<'a> {
+  fn from(data: Hist<'a>) -> PlotData<'a> {
+    PlotData::Hist(data)
+  }
+}
+
+
+/// Lengths of error bars.
+#[derive(Debug, Clone, Copy)]
+pub enum Deviation<'a> {
+  /// the same length in both directions.
+  Symmetric(&'a [f64]),
+  /// separate lengths for the lower and upper directions.
+  Asymmetric(&'a [f64], &'a [f64]),
+}
+
+impl<'a> From<&'a [f64]> for Deviation<'a> {
+  fn from(err: &'a [f64]) -> Deviation<'a> {
+    Deviation::Symmetric(err)
+  }
+}
+
+impl<'a> From<(&'a [f64], &'a [f64])> for Deviation<'a> {
+  fn from((lower, upper): (&'a [f64], &'a [f64])) -> Deviation<'a> {
+    Deviation::Asymmetric(lower, upper)
+  }
+}
+
+/// Line and/or markers with error bars.
+#[derive(Debug, Default)]
+pub struct ErrorBar<'a> {
+  xdata: &'a [f64],
+  ydata: &'a [f64],
+  xerr: Option<Deviation<'a>>,
+  yerr: Option<Deviation<'a>>,
+  capsize: Option<f64>,
+  ecolor: Option<String>,
+  label: Option<String>,
+  color: Option<String>,
+  marker: Option<String>,
+  linestyle: Option<String>,
+  linewidth: Option<f64>,
+}
+
+impl<'a> ErrorBar<'a> {
+  pub fn new(name: &str) -> ErrorBar<'a> {
+    ErrorBar::default().label(name)
+  }
+
+  pub fn data(mut self, xdata: &'a [f64], ydata: &'a [f64]) -> Self {
+    self.xdata = xdata;
+    self.ydata = ydata;
+    self
+  }
+
+  /// set the errors in x direction.
+  pub fn xerr<D: Into<Deviation<'a>>>(mut self, xerr: D) -> Self {
+    self.xerr = Some(xerr.into());
+    self
+  }
+
+  /// set the errors in y direction.
+  pub fn yerr<D: Into<Deviation<'a>>>(mut self, yerr: D) -> Self {
+    self.yerr = Some(yerr.into());
+    self
+  }
+
+  /// set the length of the caps at the end of error bars, in points.
+  pub fn capsize(mut self, size: f64) -> Self {
+    self.capsize = Some(size);
+    self
+  }
+
+  /// set the color of error bar lines.
+  pub fn ecolor(mut self, color: &str) -> Self {
+    self.ecolor = Some(color.to_owned());
+    self
+  }
+
+  pub fn label(mut self, text: &str) -> Self {
+    self.label = Some(text.to_owned());
+    self
+  }
+
+  pub fn color(mut self, color: &str) -> Self {
+    self.color = Some(color.to_owned());
+    self
+  }
+
+  pub fn marker(mut self, marker: &str) -> Self {
+    self.marker = Some(marker.to_owned());
+    self
+  }
+
+  pub fn linestyle(mut self, style: &str) -> Self {
+    self.linestyle = Some(style.to_owned());
+    self
+  }
+
+  pub fn linewidth(mut self, width: f64) -> Self {
+    self.linewidth = Some(width);
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    mpl.errorbar(self.xdata,
+                self.ydata,
+                &self.xerr,
+                &self.yerr,
+                &self.capsize,
+                &self.ecolor,
+                &self.label,
+                &self.color,
+                &self.marker,
+                &self.linestyle,
+                &self.linewidth)?;
+    Ok(())
+  }
+}
+
+impl<'a> From<ErrorBar<'a>> for PlotData<'a> {
+  fn from(data: ErrorBar<'a>) -> PlotData<'a> {
+    PlotData::ErrorBar(data)
+  }
+}
+
+
+/// 2D image (or heatmap) drawn from a row-major grid.
+#[derive(Debug, Default)]
+pub struct Image<'a> {
+  data: &'a [f64],
+  shape: (usize, usize),
+  cmap: Option<String>,
+  vmin: Option<f64>,
+  vmax: Option<f64>,
+  interpolation: Option<String>,
+  origin: Option<String>,
+  extent: Option<(f64, f64, f64, f64)>,
+}
+
+impl<'a> Image<'a> {
+  /// set the values of the image, stored in row-major order.
+  ///
+  /// `shape` is the number of rows and columns.
+  pub fn data(mut self, data: &'a [f64], shape: (usize, usize)) -> Self {
+    self.data = data;
+    self.shape = shape;
+    self
+  }
+
+  /// set the name of colormap (e.g. `"viridis"`).
+  pub fn cmap(mut self, cmap: &str) -> Self {
+    self.cmap = Some(cmap.to_owned());
+    self
+  }
+
+  /// set the range of values covered by the colormap.
+  pub fn clim(mut self, vmin: f64, vmax: f64) -> Self {
+    self.vmin = Some(vmin);
+    self.vmax = Some(vmax);
+    self
+  }
+
+  pub fn vmin(mut self, vmin: f64) -> Self {
+    self.vmin = Some(vmin);
+    self
+  }
+
+  pub fn vmax(mut self, vmax: f64) -> Self {
+    self.vmax = Some(vmax);
+    self
+  }
+
+  /// set the interpolation method (e.g. `"nearest"`, `"bilinear"`).
+  pub fn interpolation(mut self, interpolation: &str) -> Self {
+    self.interpolation = Some(interpolation.to_owned());
+    self
+  }
+
+  /// set where the first row is placed (`"upper"` or `"lower"`).
+  pub fn origin(mut self, origin: &str) -> Self {
+    self.origin = Some(origin.to_owned());
+    self
+  }
+
+  /// set the bounding box of the image in data coordinates.
+  pub fn extent(mut self, left: f64, right: f64, bottom: f64, top: f64) -> Self {
+    self.extent = Some((left, right, bottom, top));
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    check_shape(self.data, self.shape)?;
+    mpl.imshow(self.data,
+              self.shape,
+              &self.cmap,
+              &self.vmin,
+              &self.vmax,
+              &self.interpolation,
+              &self.origin,
+              &self.extent)?;
+    Ok(())
+  }
+}
+
+impl<'a> From<Image<'a>> for PlotData<'a> {
+  fn from(data: Image<'a>) -> PlotData<'a> {
+    PlotData::Image(data)
+  }
+}
+
+
+/// Specification of the levels of contour lines (or regions).
+#[derive(Debug, Clone, Copy)]
+pub enum Levels<'a> {
+  /// the number of levels, chosen automatically.
+  Count(usize),
+  /// explicit level values, in increasing order.
+  Values(&'a [f64]),
+}
+
+impl<'a> From<usize> for Levels<'a> {
+  fn from(n: usize) -> Levels<'a> {
+    Levels::Count(n)
+  }
+}
+
+impl<'a> From<&'a [f64]> for Levels<'a> {
+  fn from(values: &'a [f64]) -> Levels<'a> {
+    Levels::Values(values)
+  }
+}
+
+/// Contour lines of a scalar field.
+#[derive(Debug, Default)]
+pub struct Contour<'a> {
+  x: &'a [f64],
+  y: &'a [f64],
+  z: &'a [f64],
+  levels: Option<Levels<'a>>,
+  cmap: Option<String>,
+  linewidths: Option<f64>,
+  clabel: bool,
+}
+
+impl<'a> Contour<'a> {
+  /// set the grid coordinates and the values on the grid.
+  ///
+  /// `z` is stored in row-major order, with `y.len()` rows and `x.len()` columns.
+  pub fn data(mut self, x: &'a [f64], y: &'a [f64], z: &'a [f64]) -> Self {
+    self.x = x;
+    self.y = y;
+    self.z = z;
+    self
+  }
+
+  /// set the number of levels, or the level values.
+  pub fn levels<L: Into<Levels<'a>>>(mut self, levels: L) -> Self {
+    self.levels = Some(levels.into());
+    self
+  }
+
+  /// set the name of colormap.
+  pub fn cmap(mut self, cmap: &str) -> Self {
+    self.cmap = Some(cmap.to_owned());
+    self
+  }
+
+  pub fn linewidths(mut self, width: f64) -> Self {
+    self.linewidths = Some(width);
+    self
+  }
+
+  /// set whether the level values are labeled inline on the contour lines.
+  pub fn clabel(mut self, enabled: bool) -> Self {
+    self.clabel = enabled;
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    check_shape(self.z, (self.y.len(), self.x.len()))?;
+    mpl.contour(self.x,
+               self.y,
+               self.z,
+               &self.levels,
+               &self.cmap,
+               &self.linewidths,
+               self.clabel)?;
+    Ok(())
+  }
+}
+
+impl<'a> From<Contour<'a>> for PlotData<'a> {
+  fn from(data: Contour<'a>) -> PlotData<'a> {
+    PlotData::Contour(data)
+  }
+}
+
+
+/// Filled contour regions of a scalar field.
+#[derive(Debug, Default)]
+pub struct ContourFilled<'a> {
+  x: &'a [f64],
+  y: &'a [f64],
+  z: &'a [f64],
+  levels: Option<Levels<'a>>,
+  cmap: Option<String>,
+}
+
+impl<'a> ContourFilled<'a> {
+  /// set the grid coordinates and the values on the grid.
+  ///
+  /// `z` is stored in row-major order, with `y.len()` rows and `x.len()` columns.
+  pub fn data(mut self, x: &'a [f64], y: &'a [f64], z: &'a [f64]) -> Self {
+    self.x = x;
+    self.y = y;
+    self.z = z;
+    self
+  }
+
+  /// set the number of levels, or the level values.
+  pub fn levels<L: Into<Levels<'a>>>(mut self, levels: L) -> Self {
+    self.levels = Some(levels.into());
+    self
+  }
+
+  /// set the name of colormap.
+  pub fn cmap(mut self, cmap: &str) -> Self {
+    self.cmap = Some(cmap.to_owned());
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    check_shape(self.z, (self.y.len(), self.x.len()))?;
+    mpl.contourf(self.x, self.y, self.z, &self.levels, &self.cmap)?;
+    Ok(())
+  }
+}
+
+impl<'a> From<ContourFilled<'a>> for PlotData<'a> {
+  fn from(data: ContourFilled<'a>) -> PlotData<'a> {
+    PlotData::ContourFilled(data)
+  }
+}
+
+
+fn check_shape(data: &[f64], (rows, cols): (usize, usize)) -> Result<()> {
+  if data.len() != rows * cols {
+    return Err(Error::InvalidInput(format!("the length of data ({}) does not match to the \
+                                            shape ({} x {})",
+                                           data.len(),
+                                           rows,
+                                           cols)));
+  }
+  Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::{Axes2D, Colorbar, Image, Line2D, NonFinite, Scale, Scatter, filter_nonfinite};
+  use backend::{Call, Recorder};
+  use error::Error;
+
+  #[test]
+  fn nonfinite_clipped() {
+    let (x, y) = filter_nonfinite(&[0.0, 1.0, 2.0, 3.0],
+                                  &[f64::NAN, f64::INFINITY, 2.0, -f64::INFINITY],
+                                  None)
+      .unwrap();
+    assert_eq!(&x[..], &[0.0, 1.0, 2.0, 3.0]);
+    assert!(y[0].is_nan());
+    assert_eq!(&y[1..], &[2.0, 2.0, 2.0]);
+
+    let (_, y) = filter_nonfinite(&[0.0], &[f64::INFINITY], Some(NonFinite::Clip)).unwrap();
+    assert!(y[0].is_nan());
+  }
+
+  #[test]
+  fn nonfinite_masked() {
+    let (x, y) = filter_nonfinite(&[0.0, 1.0, f64::NAN, 3.0],
+                                  &[0.0, -f64::INFINITY, 2.0, 3.0],
+                                  Some(NonFinite::Mask))
+      .unwrap();
+    assert_eq!(x.len(), 4);
+    assert_eq!((x[0], y[0]), (0.0, 0.0));
+    assert!(x[1].is_nan() && y[1].is_nan());
+    assert!(x[2].is_nan() && y[2].is_nan());
+    assert_eq!((x[3], y[3]), (3.0, 3.0));
+  }
+
+  #[test]
+  fn nonfinite_dropped() {
+    let (x, y) = filter_nonfinite(&[0.0, 1.0, f64::NAN, 3.0],
+                                  &[0.0, -f64::INFINITY, 2.0, 3.0],
+                                  Some(NonFinite::Drop))
+      .unwrap();
+    assert_eq!(&x[..], &[0.0, 3.0]);
+    assert_eq!(&y[..], &[0.0, 3.0]);
+  }
+
+  #[test]
+  fn nonfinite_lengths() {
+    match filter_nonfinite(&[0.0, 1.0], &[0.0], None) {
+      Err(Error::InvalidInput(_)) => (),
+      r => panic!("unexpected result: {:?}", r),
+    }
+  }
+
+  #[test]
+  fn apply_axes_settings() {
+    let x = [0.0, 1.0];
+    let mut rec = Recorder::new();
+    Axes2D::default()
+      .add(Line2D::new("line").data(&x, &x))
+      .title("title")
+      .xlabel("x")
+      .ylabel("y")
+      .legend("best")
+      .xlim(0.0, 1.0)
+      .yscale(Scale::Log { base: 10.0 })
+      .apply(&mut rec)
+      .unwrap();
+
+    assert_eq!(rec.methods(),
+               ["plot", "title", "xlabel", "ylabel", "grid", "legend", "xlim", "yscale"]);
+    assert_eq!(rec.calls()[1],
+               Call::Title {
+                 title: "title".to_owned(),
+                 loc: None,
+                 fontsize: None,
+               });
+    assert_eq!(rec.calls()[4], Call::Grid(false));
+    assert_eq!(rec.calls()[6], Call::XLim((0.0, 1.0)));
+    assert_eq!(rec.calls()[7], Call::YScale(Scale::Log { base: 10.0 }));
+  }
+
+  #[test]
+  fn apply_nonfinite_to_data() {
+    let x = [0.0, 1.0, 2.0];
+    let y = [0.0, f64::NAN, 2.0];
+    let mut rec = Recorder::new();
+    Axes2D::default()
+      .add(Scatter::new("scatter").data(&x, &y).nonfinite(NonFinite::Drop))
+      .apply(&mut rec)
+      .unwrap();
+
+    match rec.calls()[0] {
+      Call::Scatter { ref xdata, ref ydata, ref label, .. } => {
+        assert_eq!(xdata, &[0.0, 2.0]);
+        assert_eq!(ydata, &[0.0, 2.0]);
+        assert_eq!(label.as_ref().map(|s| s.as_str()), Some("scatter"));
+      }
+      ref call => panic!("unexpected call: {:?}", call),
+    }
+  }
+
+  #[test]
+  fn colorbar_follows_mappable() {
+    let x = [0.0, 1.0];
+    let z = [0.0, 1.0, 2.0, 3.0];
+    let mut rec = Recorder::new();
+    Axes2D::default()
+      .add(Image::default().data(&z, (2, 2)))
+      .add(Line2D::new("line").data(&x, &x))
+      .colorbar(0, Colorbar::default().label("z"))
+      .apply(&mut rec)
+      .unwrap();
+
+    assert_eq!(&rec.methods()[..3], ["imshow", "colorbar", "plot"]);
+  }
+
+  #[test]
+  fn colorbar_rejects_non_mappable() {
+    let x = [0.0, 1.0];
+    let mut rec = Recorder::new();
+    let result = Axes2D::default()
+      .add(Line2D::new("line").data(&x, &x))
+      .colorbar(0, Colorbar::default())
+      .apply(&mut rec);
+
+    match result {
+      Err(Error::InvalidInput(_)) => (),
+      r => panic!("unexpected result: {:?}", r),
+    }
+    assert!(rec.calls().is_empty());
+  }
+
+  #[test]
+  fn image_shape_mismatch() {
+    let z = [0.0, 1.0, 2.0];
+    let mut rec = Recorder::new();
+    let result = Axes2D::default().add(Image::default().data(&z, (2, 2))).apply(&mut rec);
+    assert!(result.is_err());
+    assert!(rec.calls().is_empty());
+  }
+}
@@ -0,0 +1,467 @@
+use std::fmt::Write as FmtWrite;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use axes2d::Scale;
+use backend::Backend;
+use backend::scene::{Align, Axes, Scene, Series, area_runs, legend_position, number, segments};
+use error::{Error, Result};
+use savefig::{Format, SaveOptions};
+
+/// Draws figures by gnuplot, instead of matplotlib.
+///
+/// Each `savefig()` (or `show()`) runs a gnuplot process with the script which draws the whole
+/// figure, with the data embedded as datablocks (gnuplot 5.0 or later is required).
+/// Only `Line2D`, `Scatter` and `FillBetween` are supported.
+#[derive(Debug, Clone)]
+pub struct Gnuplot {
+  scene: Scene,
+  program: String,
+}
+
+impl Default for Gnuplot {
+  fn default() -> Gnuplot {
+    Gnuplot {
+      scene: Scene::default(),
+      program: "gnuplot".to_owned(),
+    }
+  }
+}
+
+impl Gnuplot {
+  pub fn new() -> Gnuplot {
+    Gnuplot::default()
+  }
+
+  /// set the path of gnuplot executable (`gnuplot` by default).
+  pub fn program<S: Into<String>>(mut self, program: S) -> Self {
+    self.program = program.into();
+    self
+  }
+
+  /// generate the gnuplot script which saves the current figure into `filename`.
+  ///
+  /// the output format is `options.format`, or inferred from the extension of filename.
+  pub fn script(&self, filename: &str, options: &SaveOptions) -> Result<String> {
+    let format = match options.format {
+      Some(format) => format,
+      None => format_of(filename)?,
+    };
+    let mut script = format!("set terminal {}\n", self.terminal(format, options));
+    script += &format!("set output {}\n", quote(filename));
+    script += &self.plot_script()?;
+    script += "unset output\n";
+    Ok(script)
+  }
+
+  fn terminal(&self, format: Format, options: &SaveOptions) -> String {
+    let (width, height) = self.scene.figsize();
+    let dpi = options.dpi.unwrap_or_else(|| self.scene.dpi());
+    let mut terminal = match format {
+      Format::Png => {
+        format!("pngcairo noenhanced size {},{}",
+                (width * dpi).round(),
+                (height * dpi).round())
+      }
+      // same as matplotlib, SVG images are measured in points.
+      Format::Svg => {
+        format!("svg noenhanced size {},{}",
+                (width * 72.0).round(),
+                (height * 72.0).round())
+      }
+      Format::Pdf => format!("pdfcairo noenhanced size {}in,{}in", width, height),
+      Format::Eps => format!("epscairo noenhanced size {}in,{}in", width, height),
+    };
+    if options.transparent {
+      if format == Format::Png {
+        terminal += " transparent";
+      }
+    } else if let Some(ref facecolor) = self.scene.facecolor {
+      terminal += &format!(" background {}", quote(facecolor));
+    }
+    terminal
+  }
+
+  /// generate the script which draws all axes on the current terminal.
+  fn plot_script(&self) -> Result<String> {
+    let mut script = String::new();
+    let mut plots = Vec::new();
+    for (i, axes) in self.scene.axes.iter().enumerate() {
+      plots.push(axes_script(&mut script, i + 1, axes)?);
+    }
+
+    let top = if self.scene.suptitle.is_some() { 0.93 } else { 1.0 };
+    script += "set multiplot";
+    if let Some(ref suptitle) = self.scene.suptitle {
+      script += &format!(" title {}", quote(suptitle));
+    }
+    script.push('\n');
+    for (axes, plot) in self.scene.axes.iter().zip(plots) {
+      let (row, col) = axes.cell();
+      let (width, height) = (1.0 / axes.cols as f64, top / axes.rows as f64);
+      script += "reset\n";
+      let _ = writeln!(script,
+                       "set origin {},{}",
+                       number(col as f64 * width),
+                       number(top - (row + 1) as f64 * height));
+      let _ = writeln!(script, "set size {},{}", number(width), number(height));
+      script += &plot;
+    }
+    script += "unset multiplot\n";
+    Ok(script)
+  }
+
+  /// run gnuplot with the script, and returns its standard output.
+  fn run(&self, script: String, args: &[&str]) -> Result<Vec<u8>> {
+    let mut child = Command::new(&self.program).args(args)
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .map_err(|err| match err.kind() {
+                 ErrorKind::NotFound => {
+                   Error::NotAvailable(format!("failed to launch {}: {}", self.program, err))
+                 }
+                 _ => Error::Io(err),
+               })?;
+    // the script is written in another thread, so that the output does not block gnuplot.
+    let writer = child.stdin.take().map(|mut stdin| {
+      thread::spawn(move || stdin.write_all(script.as_bytes()))
+    });
+    let output = child.wait_with_output()?;
+    if let Some(writer) = writer {
+      // a broken pipe means that gnuplot has exited, which is reported below.
+      let _ = writer.join();
+    }
+    if !output.status.success() {
+      let message = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+      return Err(Error::Backend { name: "gnuplot".to_owned(), message });
+    }
+    Ok(output.stdout)
+  }
+}
+
+impl Backend for Gnuplot {
+  delegate_to_scene!();
+
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.savefig_with(filename, &SaveOptions::default())
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    let script = self.script(filename, options)?;
+    self.run(script, &[])?;
+    Ok(self)
+  }
+
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    let format = options.format.unwrap_or(Format::Png);
+    // without `set output`, gnuplot writes the image into the standard output.
+    let mut script = format!("set terminal {}\n", self.terminal(format, options));
+    script += &self.plot_script()?;
+    self.run(script, &[])
+  }
+
+  /// show the figure in a window of the default terminal of gnuplot.
+  fn show(&mut self) -> Result<&mut Self> {
+    let script = self.plot_script()?;
+    self.run(script, &["-persist"])?;
+    Ok(self)
+  }
+}
+
+
+/// append the datablocks of axes to `data`, and returns the commands to draw the axes.
+fn axes_script(data: &mut String, id: usize, axes: &Axes) -> Result<String> {
+  let mut script = String::new();
+  if let Some(ref title) = axes.title {
+    let _ = writeln!(script, "set title {}", quote(title));
+  }
+  if let Some(ref xlabel) = axes.xlabel {
+    let _ = writeln!(script, "set xlabel {}", quote(xlabel));
+  }
+  if let Some(ref ylabel) = axes.ylabel {
+    let _ = writeln!(script, "set ylabel {}", quote(ylabel));
+  }
+  if axes.grid {
+    script += "set grid\n";
+  }
+  match axes.legend {
+    Some(ref loc) => {
+      let (horizontal, vertical) = legend_position(loc);
+      let _ = writeln!(script,
+                       "set key inside {} {} opaque box",
+                       match vertical {
+                         Align::Start => "bottom",
+                         Align::Center => "center",
+                         Align::End => "top",
+                       },
+                       match horizontal {
+                         Align::Start => "left",
+                         Align::Center => "center",
+                         Align::End => "right",
+                       });
+    }
+    None => script += "unset key\n",
+  }
+  for &(name, scale, limits, range) in &[("x", axes.xscale, axes.xlim, axes.xrange()),
+                                          ("y", axes.yscale, axes.ylim, axes.yrange())] {
+    match scale {
+      Scale::Linear => (),
+      Scale::Log { base } => {
+        let _ = writeln!(script, "set logscale {} {}", name, number(base));
+      }
+      _ => {
+        return Err(Error::Unsupported(format!("gnuplot does not support the scale {:?}", scale)))
+      }
+    }
+    // the margins of linear axes are added in the same way as matplotlib.
+    let range = match scale {
+      Scale::Linear => Some(range),
+      _ => limits,
+    };
+    if let Some((lb, ub)) = range {
+      let _ = writeln!(script, "set {}range [{}:{}]", name, number(lb), number(ub));
+    }
+  }
+
+  let mut clauses = Vec::new();
+  for (i, series) in axes.series.iter().enumerate() {
+    let block = format!("$axes{}_{}", id, i + 1);
+    let title = match series.label() {
+      Some(label) => format!("title {}", quote(label)),
+      None => "notitle".to_owned(),
+    };
+    let color = format!("lc rgb {}", quote(series.color()));
+    match *series {
+      Series::Line { ref x, ref y, ref marker, ref linestyle, linewidth, .. } => {
+        let dashtype = dashtype(linestyle)?;
+        let style = match (dashtype, marker.as_deref()) {
+          (None, None) => continue,
+          (None, Some(marker)) => format!("points {}", pointtype(marker)),
+          (Some(dt), None) => format!("lines {}", dt),
+          (Some(dt), Some(marker)) => format!("linespoints {} {}", dt, pointtype(marker)),
+        };
+        datablock(data, &block, segments(x, y).iter().map(|s| points(s)), "\n");
+        clauses.push(format!("{} using 1:2 with {} {} lw {} {}",
+                             block,
+                             style,
+                             color,
+                             number(linewidth.unwrap_or(1.5)),
+                             title));
+      }
+      Series::Scatter { ref x, ref y, ref marker, .. } => {
+        let marker = marker.as_deref().unwrap_or("o");
+        datablock(data, &block, segments(x, y).iter().map(|s| points(s)), "\n");
+        clauses.push(format!("{} using 1:2 with points {} {} {}",
+                             block,
+                             pointtype(marker),
+                             color,
+                             title));
+      }
+      Series::Area { ref x, ref y1, ref y2, ref where_, .. } => {
+        let runs = area_runs(x, y1, y2, where_);
+        // the runs are separated by double blank lines, to be selected by `index`.
+        datablock(data,
+                  &block,
+                  runs.iter().map(|run| {
+                    run.iter().map(|&(x, y1, y2)| format!("{} {} {}\n", number(x), number(y1), number(y2))).collect()
+                  }),
+                  "\n\n");
+        for index in 0..runs.len() {
+          // the legend shows the first run only.
+          clauses.push(format!("{} index {} using 1:2:3 with filledcurves {} fs solid 1.0 \
+                                noborder {}",
+                               block,
+                               index,
+                               color,
+                               if index == 0 { &title } else { "notitle" }));
+        }
+      }
+    }
+  }
+  if clauses.is_empty() {
+    // draw the empty axes.
+    clauses.push("NaN notitle".to_owned());
+  }
+  let _ = writeln!(script, "plot {}", clauses.join(", \\\n     "));
+  Ok(script)
+}
+
+fn datablock<I: Iterator<Item = String>>(data: &mut String, name: &str, chunks: I, sep: &str) {
+  let chunks: Vec<_> = chunks.collect();
+  let _ = write!(data, "{} << EOD\n{}EOD\n", name, chunks.join(sep));
+}
+
+fn points(points: &[(f64, f64)]) -> String {
+  points.iter().map(|&(x, y)| format!("{} {}\n", number(x), number(y))).collect()
+}
+
+/// the output format inferred from the extension of filename.
+fn format_of(filename: &str) -> Result<Format> {
+  let extension = Path::new(filename)
+    .extension()
+    .and_then(|e| e.to_str())
+    .map(|e| e.to_lowercase());
+  match extension.as_deref() {
+    None | Some("png") => Ok(Format::Png),
+    Some("svg") => Ok(Format::Svg),
+    Some("pdf") => Ok(Format::Pdf),
+    Some("eps") => Ok(Format::Eps),
+    Some(ext) => Err(Error::Unsupported(format!("cannot save the figure as {}", ext))),
+  }
+}
+
+/// convert the line style into the `dashtype` of gnuplot.
+///
+/// returns `None` if the line is not drawn.
+fn dashtype(linestyle: &Option<String>) -> Result<Option<&'static str>> {
+  match linestyle.as_deref() {
+    None | Some("-") | Some("solid") => Ok(Some("dt 1")),
+    Some("--") | Some("dashed") => Ok(Some("dt 2")),
+    Some(":") | Some("dotted") => Ok(Some("dt 3")),
+    Some("-.") | Some("dashdot") => Ok(Some("dt 4")),
+    Some("") | Some(" ") | Some("None") | Some("none") => Ok(None),
+    Some(style) => Err(Error::InvalidInput(format!("unknown line style: {}", style))),
+  }
+}
+
+/// convert the marker into the `pointtype` (and `pointsize`) of gnuplot.
+fn pointtype(marker: &str) -> &'static str {
+  match marker {
+    "." => "pt 7 ps 0.5",
+    "," => "pt 0",
+    "+" => "pt 1",
+    "x" => "pt 2",
+    "*" => "pt 3",
+    "s" => "pt 5",
+    "^" => "pt 9",
+    "v" => "pt 11",
+    "D" | "d" => "pt 13",
+    _ => "pt 7",
+  }
+}
+
+/// encode a string as a single-quoted string literal of gnuplot.
+///
+/// unlike double-quoted strings, backquotes in single-quoted strings are not executed as shell
+/// commands. the newlines are concatenated as `"\n"`, and the other control characters are removed.
+fn quote(text: &str) -> String {
+  let lines: Vec<_> = text.split('\n')
+    .map(|line| {
+      let mut quoted = String::with_capacity(line.len() + 2);
+      quoted.push('\'');
+      for c in line.chars() {
+        match c {
+          '\'' => quoted += "''",
+          c if c.is_control() => (),
+          c => quoted.push(c),
+        }
+      }
+      quoted.push('\'');
+      quoted
+    })
+    .collect();
+  lines.join(".\"\\n\".")
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::{Gnuplot, quote};
+  use axes2d::{Axes2D, FillBetween, Line2D, Scale, Scatter};
+  use backend::Backend;
+  use error::Error;
+  use figure::{Figure, Subplots};
+  use savefig::{Format, SaveOptions};
+
+  #[test]
+  fn generate_script() {
+    let x = [0.0, 1.0, 2.0];
+    let y = [0.0, f64::NAN, 2.0];
+    let mut gnuplot = Gnuplot::new();
+    Figure::default()
+      .suptitle("summary")
+      .subplots(Subplots::new(2, 1)
+                  .at(0,
+                      Axes2D::default()
+                        .add(Line2D::new("`touch pwned`").data(&x, &y).linestyle("--"))
+                        .add(Scatter::new("points").data(&x, &x).marker("s"))
+                        .xlabel("x")
+                        .grid(true)
+                        .legend("lower left")
+                        .xlim(0.0, 4.0))
+                  .at(1,
+                      Axes2D::default()
+                        .add(FillBetween::default().data(&x, &x, &y).label("band"))
+                        .legend("best")))
+      .apply(&mut gnuplot)
+      .unwrap();
+    let script = gnuplot.script("out.png", &SaveOptions::default()).unwrap();
+
+    assert!(script.starts_with("set terminal pngcairo noenhanced size 640,480\n\
+                                set output 'out.png'\n"));
+    // the NaN splits the line into two segments.
+    assert!(script.contains("$axes1_1 << EOD\n0 0\n\n2 2\nEOD\n"));
+    assert!(script.contains("set multiplot title 'summary'\n"));
+    assert!(script.contains("set origin 0,0.465\nset size 1,0.465\n"));
+    assert!(script.contains("set xrange [0:4]\n"));
+    assert!(script.contains("set key inside bottom left opaque box\n"));
+    // the backquotes in the label are not executed by gnuplot.
+    assert!(script.contains("plot $axes1_1 using 1:2 with lines dt 2 lc rgb '#1f77b4' lw 1.5 \
+                             title '`touch pwned`'"));
+    assert!(script.contains("$axes1_2 using 1:2 with points pt 5 lc rgb '#ff7f0e' title 'points'"));
+    // the area is split by NaN, but shown in the legend once.
+    assert!(script.contains("plot $axes2_1 index 0 using 1:2:3 with filledcurves lc rgb '#1f77b4' \
+                             fs solid 1.0 noborder title 'band'"));
+    assert!(script.contains("$axes2_1 index 1 using 1:2:3 with filledcurves lc rgb '#1f77b4' \
+                             fs solid 1.0 noborder notitle"));
+    assert!(script.ends_with("unset multiplot\nunset output\n"));
+  }
+
+  #[test]
+  fn terminals() {
+    let mut gnuplot = Gnuplot::new();
+    gnuplot.figure(&Some((4.0, 3.0)), &None, &Some("white".to_owned()), &None).unwrap();
+    let script = gnuplot.script("out.pdf", &SaveOptions::default()).unwrap();
+    assert!(script.starts_with("set terminal pdfcairo noenhanced size 4in,3in background 'white'\n"));
+    let options = SaveOptions::default().format(Format::Svg).transparent(true);
+    let script = gnuplot.script("out", &options).unwrap();
+    assert!(script.starts_with("set terminal svg noenhanced size 288,216\n"));
+    assert!(gnuplot.script("out.jpg", &SaveOptions::default()).is_err());
+  }
+
+  #[test]
+  fn log_scale() {
+    let x = [1.0, 10.0];
+    let mut gnuplot = Gnuplot::new();
+    Axes2D::default()
+      .add(Line2D::new("").data(&x, &x))
+      .yscale(Scale::Log { base: 10.0 })
+      .apply(&mut gnuplot)
+      .unwrap();
+    let script = gnuplot.script("out.png", &SaveOptions::default()).unwrap();
+    assert!(script.contains("set logscale y 10\n"));
+    assert!(!script.contains("set yrange"));
+
+    gnuplot.xscale(&Scale::Logit).unwrap();
+    assert!(gnuplot.script("out.png", &SaveOptions::default()).is_err());
+  }
+
+  #[test]
+  fn missing_program() {
+    let mut gnuplot = Gnuplot::new().program("/nonexistent/gnuplot");
+    match gnuplot.savefig("out.png") {
+      Err(Error::NotAvailable(_)) => (),
+      r => panic!("unexpected result: {:?}", r.map(|_| ())),
+    }
+  }
+
+  #[test]
+  fn quoting() {
+    assert_eq!(quote("a\"b\\c\nit's"), "'a\"b\\c'.\"\\n\".'it''s'");
+    // backquotes are not executed in single-quoted strings.
+    assert_eq!(quote("`rm -rf ~`"), "'`rm -rf ~`'");
+  }
+}
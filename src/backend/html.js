@@ -0,0 +1,620 @@
+(function () {
+  "use strict";
+
+  // the document is laid out as: the figure, the data, and this script.
+  var script = document.currentScript;
+  var figure = JSON.parse(script.previousElementSibling.textContent);
+  var container = script.previousElementSibling.previousElementSibling;
+
+  var FONT_SIZE = 10;
+  var pt = figure.pt;
+  var font = FONT_SIZE * pt;
+  var fontFamily = "sans-serif";
+  var ratio = window.devicePixelRatio || 1;
+
+  container.style.position = "relative";
+  container.style.width = figure.width + "px";
+  container.style.height = figure.height + "px";
+  container.style.background = figure.background;
+  container.style.userSelect = "none";
+
+  function createCanvas() {
+    var canvas = document.createElement("canvas");
+    canvas.width = Math.round(figure.width * ratio);
+    canvas.height = Math.round(figure.height * ratio);
+    canvas.style.position = "absolute";
+    canvas.style.left = "0";
+    canvas.style.top = "0";
+    canvas.style.width = figure.width + "px";
+    canvas.style.height = figure.height + "px";
+    container.appendChild(canvas);
+    var ctx = canvas.getContext("2d");
+    ctx.setTransform(ratio, 0, 0, ratio, 0, 0);
+    return { canvas: canvas, ctx: ctx };
+  }
+
+  var base = createCanvas();
+  // the highlighted point is drawn into the overlay, which also receives the mouse events.
+  var overlay = createCanvas();
+  var tooltip = document.createElement("div");
+  tooltip.style.cssText = "position:absolute;display:none;pointer-events:none;white-space:pre;" +
+    "padding:3px 6px;background:rgba(255,255,255,0.9);border:1px solid #999;border-radius:3px;" +
+    "font:" + Math.round(0.9 * font) + "px " + fontFamily + ";";
+  container.appendChild(tooltip);
+
+  // scales, which map the values into the coordinates where the axis is linear.
+  function makeScale(spec) {
+    if (spec.type === "log") {
+      var logBase = Math.log(spec.base);
+      return {
+        log: true,
+        base: spec.base,
+        forward: function (v) { return v > 0 ? Math.log(v) / logBase : NaN; },
+        inverse: function (u) { return Math.pow(spec.base, u); }
+      };
+    }
+    return {
+      log: false,
+      forward: function (v) { return typeof v === "number" ? v : NaN; },
+      inverse: function (u) { return u; }
+    };
+  }
+
+  // the range of finite values with 5% margins, same as matplotlib.
+  function autoscale(values) {
+    var lo = Infinity, hi = -Infinity;
+    values.forEach(function (v) {
+      if (Number.isFinite(v)) {
+        lo = Math.min(lo, v);
+        hi = Math.max(hi, v);
+      }
+    });
+    if (lo > hi) {
+      return [0, 1];
+    }
+    if (lo === hi) {
+      var delta = lo === 0 ? 0.5 : Math.abs(lo) * 0.05;
+      return [lo - delta, hi + delta];
+    }
+    var margin = (hi - lo) * 0.05;
+    return [lo - margin, hi + margin];
+  }
+
+  function initialRange(scale, limits, values) {
+    if (limits) {
+      return [scale.forward(limits[0]), scale.forward(limits[1])];
+    }
+    return autoscale(values.map(scale.forward));
+  }
+
+  // "nice" tick values (multiples of 1, 2, 2.5 or 5 times a power of 10), with the step.
+  function niceTicks(lo, hi, maxTicks) {
+    if (lo > hi) {
+      var t = lo; lo = hi; hi = t;
+    }
+    var span = hi - lo;
+    if (!(span > 0) || !Number.isFinite(span)) {
+      return { values: [lo], step: 1 };
+    }
+    var raw = span / Math.max(maxTicks, 1);
+    var magnitude = Math.pow(10, Math.floor(Math.log10(raw)));
+    var step = 10 * magnitude;
+    [1, 2, 2.5, 5].some(function (m) {
+      if (m * magnitude >= raw) {
+        step = m * magnitude;
+        return true;
+      }
+      return false;
+    });
+    var values = [];
+    for (var i = Math.ceil(lo / step - 1e-9); i <= Math.floor(hi / step + 1e-9); i++) {
+      values.push(i * step + 0);
+    }
+    return { values: values, step: step };
+  }
+
+  function decimals(step) {
+    for (var d = 0; d < 10; d++) {
+      var scaled = step * Math.pow(10, d);
+      if (Math.abs(scaled - Math.round(scaled)) < 1e-6 * Math.max(scaled, 1)) {
+        return d;
+      }
+    }
+    return 10;
+  }
+
+  // the ticks of an axis as the pairs of coordinate and label.
+  function ticks(scale, range, maxTicks) {
+    var lo = Math.min(range[0], range[1]), hi = Math.max(range[0], range[1]);
+    if (scale.log && hi - lo >= 1) {
+      var step = Math.max(1, Math.ceil((hi - lo) / maxTicks));
+      var result = [];
+      for (var k = Math.ceil(lo / step) * step; k <= hi; k += step) {
+        var value = scale.inverse(k);
+        var label = Math.abs(k) < 4 ? String(Number(value.toPrecision(6))) : value.toExponential(0);
+        result.push([k, label]);
+      }
+      return result;
+    }
+    var nice = niceTicks(scale.inverse(lo), scale.inverse(hi), maxTicks);
+    var d = decimals(nice.step);
+    return nice.values
+      .filter(function (v) { return !scale.log || v > 0; })
+      .map(function (v) { return [scale.forward(v), v.toFixed(d)]; });
+  }
+
+  // the number of ticks, so that the interval is at least `spacing` pixels.
+  function maxTicks(length, spacing) {
+    return Math.min(Math.max(Math.floor(length / spacing), 2), 10);
+  }
+
+  function formatValue(v) {
+    return String(Number(v.toPrecision(6)));
+  }
+
+  function setFont(ctx, size) {
+    ctx.font = (size * pt) + "px " + fontFamily;
+  }
+
+  // draw a marker (in matplotlib's notation) centered at (x, y).
+  function drawMarker(ctx, marker, x, y, size, color) {
+    var r = size / 2;
+    ctx.fillStyle = color;
+    ctx.strokeStyle = color;
+    var polygon = function (points) {
+      ctx.beginPath();
+      points.forEach(function (p, i) {
+        if (i === 0) {
+          ctx.moveTo(x + p[0] * r, y + p[1] * r);
+        } else {
+          ctx.lineTo(x + p[0] * r, y + p[1] * r);
+        }
+      });
+      ctx.closePath();
+      ctx.fill();
+    };
+    switch (marker) {
+      case "None": case "none": case "": case " ":
+        return;
+      case ",":
+        ctx.fillRect(x - 0.5, y - 0.5, 1, 1);
+        return;
+      case ".":
+        r /= 2;
+        break;
+      case "s":
+        return polygon([[-1, -1], [1, -1], [1, 1], [-1, 1]]);
+      case "D": case "d":
+        return polygon([[0, -1.2], [1, 0], [0, 1.2], [-1, 0]]);
+      case "^":
+        return polygon([[0, -1], [1, 1], [-1, 1]]);
+      case "v":
+        return polygon([[0, 1], [1, -1], [-1, -1]]);
+      case "<":
+        return polygon([[-1, 0], [1, -1], [1, 1]]);
+      case ">":
+        return polygon([[1, 0], [-1, -1], [-1, 1]]);
+      case "x": case "+":
+        var a = r, b = marker === "x" ? r : 0;
+        ctx.lineWidth = size / 6;
+        ctx.setLineDash([]);
+        ctx.beginPath();
+        ctx.moveTo(x - a, y - b);
+        ctx.lineTo(x + a, y + b);
+        ctx.moveTo(x - b, y + a);
+        ctx.lineTo(x + b, y - a);
+        ctx.stroke();
+        return;
+    }
+    ctx.beginPath();
+    ctx.arc(x, y, r, 0, 2 * Math.PI);
+    ctx.fill();
+  }
+
+  function Axes(spec) {
+    this.spec = spec;
+    this.xscale = makeScale(spec.xscale);
+    this.yscale = makeScale(spec.yscale);
+    var xs = [], ys = [];
+    spec.series.forEach(function (s) {
+      if (s.type === "area") {
+        s.runs.forEach(function (run) {
+          run.forEach(function (p) {
+            xs.push(p[0]);
+            ys.push(p[1], p[2]);
+          });
+        });
+      } else {
+        Array.prototype.push.apply(xs, s.x);
+        Array.prototype.push.apply(ys, s.y);
+      }
+    });
+    this.initial = {
+      x: initialRange(this.xscale, spec.xlim, xs),
+      y: initialRange(this.yscale, spec.ylim, ys)
+    };
+    this.reset();
+
+    var top = figure.suptitle !== null ? 2.5 * font : 0;
+    var cellWidth = figure.width / spec.cols;
+    var cellHeight = (figure.height - top) / spec.rows;
+    var cellX = spec.col * cellWidth, cellY = top + spec.row * cellHeight;
+    var titleSpace = spec.title !== null ? 2 * font : 0;
+    this.area = {
+      x: cellX + 5.5 * font,
+      y: cellY + font + titleSpace,
+      width: Math.max(cellWidth - 7.5 * font, 1),
+      height: Math.max(cellHeight - 5 * font - titleSpace, 1)
+    };
+  }
+
+  Axes.prototype.reset = function () {
+    this.x = this.initial.x.slice();
+    this.y = this.initial.y.slice();
+  };
+
+  Axes.prototype.contains = function (px, py) {
+    var a = this.area;
+    return px >= a.x && px <= a.x + a.width && py >= a.y && py <= a.y + a.height;
+  };
+
+  // map the coordinates, where the axes are linear, into the pixels.
+  Axes.prototype.ux = function (u) {
+    return this.area.x + (u - this.x[0]) / (this.x[1] - this.x[0]) * this.area.width;
+  };
+
+  Axes.prototype.uy = function (u) {
+    var a = this.area;
+    return a.y + a.height - (u - this.y[0]) / (this.y[1] - this.y[0]) * a.height;
+  };
+
+  // map the values into the pixels.
+  Axes.prototype.px = function (v) {
+    return this.ux(this.xscale.forward(v));
+  };
+
+  Axes.prototype.py = function (v) {
+    return this.uy(this.yscale.forward(v));
+  };
+
+  Axes.prototype.draw = function (ctx) {
+    var self = this, spec = this.spec, a = this.area;
+    var x0 = a.x, y0 = a.y, x1 = a.x + a.width, y1 = a.y + a.height;
+    var xticks = ticks(this.xscale, this.x, maxTicks(a.width, 6 * font))
+      .map(function (t) { return [self.ux(t[0]), t[1]]; })
+      .filter(function (t) { return t[0] > x0 - 0.5 && t[0] < x1 + 0.5; });
+    var yticks = ticks(this.yscale, this.y, maxTicks(a.height, 3 * font))
+      .map(function (t) { return [self.uy(t[0]), t[1]]; })
+      .filter(function (t) { return t[0] > y0 - 0.5 && t[0] < y1 + 0.5; });
+
+    ctx.fillStyle = "white";
+    ctx.fillRect(x0, y0, a.width, a.height);
+    if (spec.grid) {
+      ctx.strokeStyle = "#b0b0b0";
+      ctx.lineWidth = 0.8 * pt;
+      ctx.setLineDash([]);
+      ctx.beginPath();
+      xticks.forEach(function (t) { ctx.moveTo(t[0], y0); ctx.lineTo(t[0], y1); });
+      yticks.forEach(function (t) { ctx.moveTo(x0, t[0]); ctx.lineTo(x1, t[0]); });
+      ctx.stroke();
+    }
+
+    ctx.save();
+    ctx.beginPath();
+    ctx.rect(x0, y0, a.width, a.height);
+    ctx.clip();
+    spec.series.forEach(function (s) { self.drawSeries(ctx, s); });
+    ctx.restore();
+
+    // spines and ticks.
+    ctx.strokeStyle = "black";
+    ctx.lineWidth = 0.8 * pt;
+    ctx.setLineDash([]);
+    ctx.strokeRect(x0, y0, a.width, a.height);
+    var tick = 3.5 * pt;
+    ctx.beginPath();
+    xticks.forEach(function (t) { ctx.moveTo(t[0], y1); ctx.lineTo(t[0], y1 + tick); });
+    yticks.forEach(function (t) { ctx.moveTo(x0 - tick, t[0]); ctx.lineTo(x0, t[0]); });
+    ctx.stroke();
+
+    ctx.fillStyle = "black";
+    setFont(ctx, FONT_SIZE);
+    ctx.textAlign = "center";
+    xticks.forEach(function (t) { ctx.fillText(t[1], t[0], y1 + tick + 1.2 * font); });
+    ctx.textAlign = "right";
+    yticks.forEach(function (t) {
+      ctx.fillText(t[1], x0 - tick - 0.4 * font, t[0] + 0.35 * font);
+    });
+
+    ctx.textAlign = "center";
+    if (spec.xlabel !== null) {
+      ctx.fillText(spec.xlabel, (x0 + x1) / 2, y1 + tick + 2.8 * font);
+    }
+    if (spec.ylabel !== null) {
+      ctx.save();
+      ctx.translate(x0 - 4.5 * font, (y0 + y1) / 2);
+      ctx.rotate(-Math.PI / 2);
+      ctx.fillText(spec.ylabel, 0, 0);
+      ctx.restore();
+    }
+    if (spec.title !== null) {
+      setFont(ctx, 1.2 * FONT_SIZE);
+      var loc = { left: x0, center: (x0 + x1) / 2, right: x1 };
+      var align = spec.titleLoc in loc ? spec.titleLoc : "center";
+      ctx.textAlign = align;
+      ctx.fillText(spec.title, loc[align], y0 - 0.6 * font);
+    }
+    if (spec.legend !== null) {
+      this.drawLegend(ctx);
+    }
+  };
+
+  Axes.prototype.drawSeries = function (ctx, s) {
+    var self = this;
+    if (s.type === "area") {
+      ctx.fillStyle = s.color;
+      s.runs.forEach(function (run) {
+        ctx.beginPath();
+        run.forEach(function (p, i) {
+          if (i === 0) {
+            ctx.moveTo(self.px(p[0]), self.py(p[1]));
+          } else {
+            ctx.lineTo(self.px(p[0]), self.py(p[1]));
+          }
+        });
+        for (var i = run.length - 1; i >= 0; i--) {
+          ctx.lineTo(self.px(run[i][0]), self.py(run[i][2]));
+        }
+        ctx.closePath();
+        ctx.fill();
+      });
+      return;
+    }
+    var points = s.x.map(function (x, i) { return [self.px(x), self.py(s.y[i])]; });
+    if (s.type === "line" && s.dash !== null) {
+      ctx.strokeStyle = s.color;
+      ctx.lineWidth = s.width * pt;
+      ctx.lineJoin = "round";
+      ctx.setLineDash(s.dash.map(function (d) { return d * pt; }));
+      ctx.beginPath();
+      var drawing = false;
+      points.forEach(function (p) {
+        if (!Number.isFinite(p[0]) || !Number.isFinite(p[1])) {
+          drawing = false;
+        } else if (drawing) {
+          ctx.lineTo(p[0], p[1]);
+        } else {
+          ctx.moveTo(p[0], p[1]);
+          drawing = true;
+        }
+      });
+      ctx.stroke();
+    }
+    if (s.marker !== null) {
+      points.forEach(function (p) {
+        if (Number.isFinite(p[0]) && Number.isFinite(p[1])) {
+          drawMarker(ctx, s.marker, p[0], p[1], 6 * pt, s.color);
+        }
+      });
+    }
+  };
+
+  Axes.prototype.drawLegend = function (ctx) {
+    // the lines drawn without both line and markers have no sample to show.
+    var entries = this.spec.series.filter(function (s) {
+      return s.label !== null && (s.type === "area" || s.dash !== null || s.marker !== null);
+    });
+    if (entries.length === 0) {
+      return;
+    }
+    var a = this.area;
+    var lineHeight = 1.5 * font, sample = 2 * font, pad = 0.5 * font;
+    setFont(ctx, FONT_SIZE);
+    var textWidth = Math.max.apply(null, entries.map(function (s) {
+      return ctx.measureText(s.label).width;
+    }));
+    var width = sample + textWidth + 1.5 * font;
+    var height = entries.length * lineHeight + 0.5 * font;
+    var x = {
+      start: a.x + pad,
+      center: a.x + (a.width - width) / 2,
+      end: a.x + a.width - width - pad
+    }[this.spec.legend.x];
+    var y = {
+      end: a.y + pad,
+      center: a.y + (a.height - height) / 2,
+      start: a.y + a.height - height - pad
+    }[this.spec.legend.y];
+
+    ctx.fillStyle = "rgba(255,255,255,0.8)";
+    ctx.strokeStyle = "#cccccc";
+    ctx.lineWidth = 0.8 * pt;
+    ctx.setLineDash([]);
+    ctx.fillRect(x, y, width, height);
+    ctx.strokeRect(x, y, width, height);
+    entries.forEach(function (s, i) {
+      var cy = y + 0.25 * font + (i + 0.5) * lineHeight;
+      var sx0 = x + 0.5 * font, sx1 = sx0 + sample;
+      if (s.type === "area") {
+        ctx.fillStyle = s.color;
+        ctx.fillRect(sx0, cy - 0.35 * font, sample, 0.7 * font);
+      } else if (s.type === "line" && s.dash !== null) {
+        ctx.strokeStyle = s.color;
+        ctx.lineWidth = s.width * pt;
+        ctx.setLineDash(s.dash.map(function (d) { return d * pt; }));
+        ctx.beginPath();
+        ctx.moveTo(sx0, cy);
+        ctx.lineTo(sx1, cy);
+        ctx.stroke();
+      }
+      if (s.type !== "area" && s.marker !== null) {
+        drawMarker(ctx, s.marker, (sx0 + sx1) / 2, cy, 6 * pt, s.color);
+      }
+      ctx.fillStyle = "black";
+      ctx.textAlign = "left";
+      ctx.fillText(s.label, sx1 + 0.5 * font, cy + 0.35 * font);
+    });
+  };
+
+  // the nearest point of lines and scatters within `radius` pixels.
+  Axes.prototype.nearest = function (px, py, radius) {
+    var self = this, best = null, bestDistance = radius * radius;
+    this.spec.series.forEach(function (s) {
+      if (s.type === "area") {
+        return;
+      }
+      s.x.forEach(function (x, i) {
+        var dx = self.px(x) - px, dy = self.py(s.y[i]) - py;
+        var distance = dx * dx + dy * dy;
+        if (distance <= bestDistance) {
+          bestDistance = distance;
+          best = { series: s, x: x, y: s.y[i] };
+        }
+      });
+    });
+    return best;
+  };
+
+  var axes = figure.axes.map(function (spec) { return new Axes(spec); });
+
+  function draw() {
+    var ctx = base.ctx;
+    ctx.clearRect(0, 0, figure.width, figure.height);
+    if (figure.suptitle !== null) {
+      ctx.fillStyle = "black";
+      setFont(ctx, 1.2 * FONT_SIZE);
+      ctx.textAlign = "center";
+      ctx.fillText(figure.suptitle, figure.width / 2, 1.5 * font);
+    }
+    axes.forEach(function (ax) { ax.draw(ctx); });
+  }
+
+  function hideTooltip() {
+    overlay.ctx.clearRect(0, 0, figure.width, figure.height);
+    tooltip.style.display = "none";
+  }
+
+  function showTooltip(ax, px, py) {
+    hideTooltip();
+    var hit = ax.nearest(px, py, 8);
+    if (hit === null) {
+      return;
+    }
+    var x = ax.px(hit.x), y = ax.py(hit.y);
+    var ctx = overlay.ctx;
+    ctx.save();
+    ctx.beginPath();
+    ctx.rect(ax.area.x, ax.area.y, ax.area.width, ax.area.height);
+    ctx.clip();
+    ctx.strokeStyle = hit.series.color;
+    ctx.lineWidth = 1.5 * pt;
+    ctx.setLineDash([]);
+    ctx.beginPath();
+    ctx.arc(x, y, 5 * pt, 0, 2 * Math.PI);
+    ctx.stroke();
+    ctx.restore();
+
+    var lines = [];
+    if (hit.series.label !== null) {
+      lines.push(hit.series.label);
+    }
+    lines.push("x: " + formatValue(hit.x), "y: " + formatValue(hit.y));
+    tooltip.textContent = lines.join("\n");
+    tooltip.style.display = "block";
+    var left = x + 10, top = y + 10;
+    if (left + tooltip.offsetWidth > figure.width) {
+      left = x - 10 - tooltip.offsetWidth;
+    }
+    if (top + tooltip.offsetHeight > figure.height) {
+      top = y - 10 - tooltip.offsetHeight;
+    }
+    tooltip.style.left = left + "px";
+    tooltip.style.top = top + "px";
+  }
+
+  function position(event) {
+    var rect = overlay.canvas.getBoundingClientRect();
+    return [event.clientX - rect.left, event.clientY - rect.top];
+  }
+
+  function axesAt(p) {
+    for (var i = axes.length - 1; i >= 0; i--) {
+      if (axes[i].contains(p[0], p[1])) {
+        return axes[i];
+      }
+    }
+    return null;
+  }
+
+  // pan by dragging.
+  var drag = null;
+  overlay.canvas.addEventListener("mousedown", function (event) {
+    var p = position(event);
+    var ax = axesAt(p);
+    if (ax !== null && event.button === 0) {
+      drag = { axes: ax, p: p, x: ax.x.slice(), y: ax.y.slice() };
+      overlay.canvas.style.cursor = "grabbing";
+      hideTooltip();
+      event.preventDefault();
+    }
+  });
+  window.addEventListener("mousemove", function (event) {
+    var p = position(event);
+    if (drag !== null) {
+      var ax = drag.axes, a = ax.area;
+      var dx = (p[0] - drag.p[0]) / a.width * (drag.x[1] - drag.x[0]);
+      var dy = (p[1] - drag.p[1]) / a.height * (drag.y[1] - drag.y[0]);
+      ax.x = [drag.x[0] - dx, drag.x[1] - dx];
+      ax.y = [drag.y[0] + dy, drag.y[1] + dy];
+      draw();
+      return;
+    }
+    var ax = event.target === overlay.canvas ? axesAt(p) : null;
+    if (ax !== null) {
+      showTooltip(ax, p[0], p[1]);
+    } else {
+      hideTooltip();
+    }
+  });
+  window.addEventListener("mouseup", function () {
+    if (drag !== null) {
+      drag = null;
+      overlay.canvas.style.cursor = "";
+    }
+  });
+  overlay.canvas.addEventListener("mouseleave", function () {
+    if (drag === null) {
+      hideTooltip();
+    }
+  });
+
+  // zoom around the pointer by the wheel.
+  overlay.canvas.addEventListener("wheel", function (event) {
+    var p = position(event);
+    var ax = axesAt(p);
+    if (ax === null) {
+      return;
+    }
+    event.preventDefault();
+    var factor = Math.exp(Math.max(-100, Math.min(100, event.deltaY)) * 0.002);
+    var a = ax.area;
+    var u = ax.x[0] + (p[0] - a.x) / a.width * (ax.x[1] - ax.x[0]);
+    var v = ax.y[0] + (a.y + a.height - p[1]) / a.height * (ax.y[1] - ax.y[0]);
+    ax.x = [u + (ax.x[0] - u) * factor, u + (ax.x[1] - u) * factor];
+    ax.y = [v + (ax.y[0] - v) * factor, v + (ax.y[1] - v) * factor];
+    draw();
+    showTooltip(ax, p[0], p[1]);
+  }, { passive: false });
+
+  // reset the view by double-click.
+  overlay.canvas.addEventListener("dblclick", function (event) {
+    var ax = axesAt(position(event));
+    if (ax !== null) {
+      ax.reset();
+      draw();
+      hideTooltip();
+    }
+  });
+
+  draw();
+})();
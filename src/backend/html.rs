@@ -0,0 +1,275 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use axes2d::Scale;
+use backend::Backend;
+use backend::json::Json;
+use backend::scene::{Align, Axes, Scene, Series, area_runs, dash_pattern, escape,
+                     legend_position};
+use error::{Error, Result};
+use savefig::SaveOptions;
+
+/// the renderer embedded into the documents, which draws the figure into a canvas.
+const RENDERER: &str = include_str!("html.js");
+
+/// Exports figures as self-contained interactive HTML documents.
+///
+/// The figure is drawn by an embedded script, so the documents can be opened offline.
+/// Each axes can be panned by dragging and zoomed by the mouse wheel (double-click to reset),
+/// and the values of `Line2D` and `Scatter` are shown in tooltips.
+/// Only `Line2D`, `Scatter` and `FillBetween` with linear or log scales are supported.
+#[derive(Debug, Default, Clone)]
+pub struct Html {
+  scene: Scene,
+}
+
+impl Html {
+  pub fn new() -> Html {
+    Html::default()
+  }
+
+  /// render the current figure into an HTML document.
+  ///
+  /// the resolution, transparency and the metadata `"Title"` in `options` are honored.
+  pub fn render(&self, options: &SaveOptions) -> Result<String> {
+    if let Some(format) = options.format {
+      return Err(Error::Unsupported(format!("cannot render the figure as {}", format.as_str())));
+    }
+    let title = options.metadata
+      .get("Title")
+      .or(self.scene.suptitle.as_ref())
+      .map(|t| t.as_str())
+      .unwrap_or("Figure");
+    let data = self.data(options)?;
+    Ok(format!("<!DOCTYPE html>\n\
+                <html>\n\
+                <head>\n\
+                <meta charset=\"utf-8\">\n\
+                <title>{}</title>\n\
+                </head>\n\
+                <body>\n\
+                <div class=\"rustplotlib-figure\"></div>\n\
+                <script type=\"application/json\" class=\"rustplotlib-data\">{}</script>\n\
+                <script>\n{}</script>\n\
+                </body>\n\
+                </html>\n",
+               escape(title),
+               data,
+               RENDERER))
+  }
+
+  /// the figure passed to the renderer.
+  fn data(&self, options: &SaveOptions) -> Result<Json> {
+    let dpi = options.dpi.unwrap_or_else(|| self.scene.dpi());
+    let (width, height) = self.scene.figsize();
+    let background = if options.transparent {
+      "transparent"
+    } else {
+      self.scene.facecolor.as_deref().unwrap_or("white")
+    };
+    let axes = self.scene.axes.iter().map(axes).collect::<Result<_>>()?;
+    Ok(Json::object(vec![("width", Json::from((width * dpi).round())),
+                         ("height", Json::from((height * dpi).round())),
+                         ("pt", Json::from(dpi / 72.0)),
+                         ("background", Json::from(background)),
+                         ("suptitle", optional(&self.scene.suptitle)),
+                         ("axes", Json::Array(axes))]))
+  }
+
+  /// write the current figure into a file as an HTML document.
+  fn write<P: AsRef<Path>>(&self, filename: P, options: &SaveOptions) -> Result<()> {
+    let html = self.render(options)?;
+    let mut file = File::create(filename)?;
+    file.write_all(html.as_bytes())?;
+    Ok(())
+  }
+}
+
+impl Backend for Html {
+  delegate_to_scene!();
+
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.savefig_with(filename, &SaveOptions::default())
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    let extension = Path::new(filename).extension().and_then(|e| e.to_str());
+    match extension {
+      None => (),
+      Some(ext) if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") => (),
+      Some(ext) => return Err(Error::Unsupported(format!("cannot save the figure as {}", ext))),
+    }
+    self.write(filename, options)?;
+    Ok(self)
+  }
+
+  /// render the current figure as an HTML document (the image formats are not supported).
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    self.render(options).map(String::into_bytes)
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    Err(Error::Unsupported("Html cannot show the figure in a window".to_owned()))
+  }
+}
+
+
+fn axes(axes: &Axes) -> Result<Json> {
+  let (row, col) = axes.cell();
+  let legend = match axes.legend {
+    Some(ref loc) => {
+      let (horizontal, vertical) = legend_position(loc);
+      Json::object(vec![("x", align(horizontal)), ("y", align(vertical))])
+    }
+    None => Json::Null,
+  };
+  let limits = |limits: Option<(f64, f64)>| match limits {
+    Some((lb, ub)) => Json::numbers(&[lb, ub]),
+    None => Json::Null,
+  };
+  let series = axes.series.iter().map(series).collect::<Result<_>>()?;
+  Ok(Json::object(vec![("rows", Json::from(axes.rows as f64)),
+                       ("cols", Json::from(axes.cols as f64)),
+                       ("row", Json::from(row as f64)),
+                       ("col", Json::from(col as f64)),
+                       ("title", optional(&axes.title)),
+                       ("titleLoc", Json::from(axes.title_loc.as_deref().unwrap_or("center"))),
+                       ("xlabel", optional(&axes.xlabel)),
+                       ("ylabel", optional(&axes.ylabel)),
+                       ("grid", Json::from(axes.grid)),
+                       ("legend", legend),
+                       ("xscale", scale(axes.xscale)?),
+                       ("yscale", scale(axes.yscale)?),
+                       ("xlim", limits(axes.xlim)),
+                       ("ylim", limits(axes.ylim)),
+                       ("series", Json::Array(series))]))
+}
+
+fn series(series: &Series) -> Result<Json> {
+  let label = match series.label() {
+    Some(label) => Json::from(label),
+    None => Json::Null,
+  };
+  let mut json = match *series {
+    Series::Line { ref x, ref y, ref marker, ref linestyle, linewidth, .. } => {
+      let linewidth = linewidth.unwrap_or(1.5);
+      let dash = match dash_pattern(linestyle, linewidth)? {
+        Some(dash) => Json::numbers(&dash),
+        None => Json::Null,
+      };
+      Json::object(vec![("type", Json::from("line")),
+                        ("x", Json::numbers(x)),
+                        ("y", Json::numbers(y)),
+                        ("width", Json::from(linewidth)),
+                        ("dash", dash),
+                        ("marker", optional(marker))])
+    }
+    Series::Scatter { ref x, ref y, ref marker, .. } => {
+      Json::object(vec![("type", Json::from("scatter")),
+                        ("x", Json::numbers(x)),
+                        ("y", Json::numbers(y)),
+                        ("marker", Json::from(marker.as_deref().unwrap_or("o")))])
+    }
+    Series::Area { ref x, ref y1, ref y2, ref where_, .. } => {
+      let runs = area_runs(x, y1, y2, where_)
+        .iter()
+        .map(|run| {
+          Json::Array(run.iter().map(|&(x, y1, y2)| Json::numbers(&[x, y1, y2])).collect())
+        })
+        .collect();
+      Json::object(vec![("type", Json::from("area")), ("runs", Json::Array(runs))])
+    }
+  };
+  json.insert("label", label);
+  json.insert("color", Json::from(series.color()));
+  Ok(json)
+}
+
+fn scale(scale: Scale) -> Result<Json> {
+  match scale {
+    Scale::Linear => Ok(Json::object(vec![("type", Json::from("linear"))])),
+    Scale::Log { base } => {
+      Ok(Json::object(vec![("type", Json::from("log")), ("base", Json::from(base))]))
+    }
+    _ => Err(Error::Unsupported("Html only supports linear and log scales".to_owned())),
+  }
+}
+
+fn align(align: Align) -> Json {
+  Json::from(match align {
+    Align::Start => "start",
+    Align::Center => "center",
+    Align::End => "end",
+  })
+}
+
+fn optional(value: &Option<String>) -> Json {
+  match *value {
+    Some(ref value) => Json::from(value.as_str()),
+    None => Json::Null,
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::Html;
+  use axes2d::{Axes2D, FillBetween, Line2D, Scale, Scatter};
+  use backend::Backend;
+  use figure::{Figure, Subplots};
+  use savefig::{Format, SaveOptions};
+
+  #[test]
+  fn render_document() {
+    let x = [0.0, 1.0, 2.0];
+    let y = [1.0, f64::NAN, 3.0];
+    let mut html = Html::new();
+    Figure::default()
+      .suptitle("</script>")
+      .subplots(Subplots::new(1, 2)
+                  .at(0,
+                      Axes2D::default()
+                        .add(Line2D::new("data").data(&x, &y).linestyle(":").marker("o"))
+                        .add(Scatter::new("_hidden").data(&x, &x))
+                        .xlabel("time")
+                        .legend("lower right"))
+                  .at(1,
+                      Axes2D::default()
+                        .add(FillBetween::default().data(&x, &x, &y))
+                        .yscale(Scale::Log { base: 2.0 })
+                        .ylim(1.0, 8.0)))
+      .apply(&mut html)
+      .unwrap();
+    let doc = html.render(&SaveOptions::default().dpi(72.0)).unwrap();
+
+    assert!(doc.starts_with("<!DOCTYPE html>"));
+    assert!(doc.contains("<title>&lt;/script&gt;</title>"));
+    // the data must not close the script element.
+    assert_eq!(doc.matches("</script>").count(), 2);
+    assert!(doc.contains(r#"{"width":461,"height":346,"pt":1,"background":"white","suptitle":"\u003c/script>","#));
+    assert!(doc.contains(r#""row":0,"col":1,"#));
+    assert!(doc.contains(r#""legend":{"x":"end","y":"start"}"#));
+    assert!(doc.contains(r#"{"type":"line","x":[0,1,2],"y":[1,null,3],"width":1.5,"dash":[1.5,2.4749999999999996],"marker":"o","label":"data","#));
+    assert!(doc.contains(r#""marker":"o","label":null,"#));
+    assert!(doc.contains(r#""yscale":{"type":"log","base":2},"xlim":null,"ylim":[1,8]"#));
+    assert!(doc.contains(r#"{"type":"area","runs":[[[0,0,1]],[[2,2,3]]],"label":null,"#));
+  }
+
+  #[test]
+  fn self_contained() {
+    let doc = Html::new().render(&SaveOptions::default()).unwrap();
+    assert!(!doc.contains("src="));
+    assert!(!doc.contains("http"));
+  }
+
+  #[test]
+  fn unsupported() {
+    let mut html = Html::new();
+    html.yscale(&Scale::Logit).unwrap();
+    assert!(html.render(&SaveOptions::default()).is_err());
+
+    let mut html = Html::new();
+    assert!(html.savefig_bytes(&SaveOptions::default().format(Format::Png)).is_err());
+    assert!(html.savefig("figure.svg").is_err());
+  }
+}
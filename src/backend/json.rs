@@ -0,0 +1,184 @@
+//! A minimal JSON value, to generate the specifications and data for web frontends.
+
+use std::fmt::{self, Write};
+
+/// A JSON value. the members of an object keep their order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+  Null,
+  Bool(bool),
+  /// a number, where non-finite values are written as `null`.
+  Number(f64),
+  String(String),
+  Array(Vec<Json>),
+  Object(Vec<(String, Json)>),
+}
+
+impl Json {
+  /// create an object from the pairs of key and value.
+  pub fn object<I, K>(members: I) -> Json
+    where I: IntoIterator<Item = (K, Json)>,
+          K: Into<String>
+  {
+    Json::Object(members.into_iter().map(|(k, v)| (k.into(), v)).collect())
+  }
+
+  /// create an array of numbers.
+  pub fn numbers(values: &[f64]) -> Json {
+    Json::Array(values.iter().map(|&v| Json::Number(v)).collect())
+  }
+
+  /// add a member to the object (does nothing for the other values).
+  pub fn insert<K: Into<String>>(&mut self, key: K, value: Json) {
+    if let Json::Object(ref mut members) = *self {
+      members.push((key.into(), value));
+    }
+  }
+
+  /// format the value with indentation, where the arrays and objects which only contain
+  /// scalars are written in a line.
+  pub fn pretty(&self) -> String {
+    let mut out = String::new();
+    let _ = self.write_pretty(&mut out, 0);
+    out.push('\n');
+    out
+  }
+
+  fn is_scalar(&self) -> bool {
+    !matches!(*self, Json::Array(_) | Json::Object(_))
+  }
+
+  fn is_flat(&self) -> bool {
+    match *self {
+      Json::Array(ref values) => values.iter().all(Json::is_scalar),
+      Json::Object(ref members) => members.iter().all(|(_, v)| v.is_scalar()),
+      _ => true,
+    }
+  }
+
+  fn write_pretty<W: Write>(&self, out: &mut W, indent: usize) -> fmt::Result {
+    if self.is_flat() {
+      return write!(out, "{}", self);
+    }
+    let pad = "  ".repeat(indent + 1);
+    match *self {
+      Json::Array(ref values) => {
+        out.write_str("[\n")?;
+        for (i, value) in values.iter().enumerate() {
+          out.write_str(&pad)?;
+          value.write_pretty(out, indent + 1)?;
+          out.write_str(if i + 1 < values.len() { ",\n" } else { "\n" })?;
+        }
+        write!(out, "{}]", "  ".repeat(indent))
+      }
+      Json::Object(ref members) => {
+        out.write_str("{\n")?;
+        for (i, (key, value)) in members.iter().enumerate() {
+          write!(out, "{}{}: ", pad, Json::String(key.clone()))?;
+          value.write_pretty(out, indent + 1)?;
+          out.write_str(if i + 1 < members.len() { ",\n" } else { "\n" })?;
+        }
+        write!(out, "{}}}", "  ".repeat(indent))
+      }
+      _ => unreachable!(),
+    }
+  }
+}
+
+impl<'a> From<&'a str> for Json {
+  fn from(s: &'a str) -> Json {
+    Json::String(s.to_owned())
+  }
+}
+
+impl From<String> for Json {
+  fn from(s: String) -> Json {
+    Json::String(s)
+  }
+}
+
+impl From<f64> for Json {
+  fn from(v: f64) -> Json {
+    Json::Number(v)
+  }
+}
+
+impl From<bool> for Json {
+  fn from(b: bool) -> Json {
+    Json::Bool(b)
+  }
+}
+
+/// write the value compactly.
+impl fmt::Display for Json {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Json::Null => f.write_str("null"),
+      Json::Bool(b) => write!(f, "{}", b),
+      Json::Number(v) if v.is_finite() => write!(f, "{}", v),
+      Json::Number(_) => f.write_str("null"),
+      Json::String(ref s) => {
+        f.write_char('"')?;
+        for c in s.chars() {
+          match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            // escaping '<' prevents "</script>" from closing the element, when embedded in HTML.
+            '<' => f.write_str("\\u003c")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+          }
+        }
+        f.write_char('"')
+      }
+      Json::Array(ref values) => {
+        f.write_char('[')?;
+        for (i, value) in values.iter().enumerate() {
+          if i > 0 {
+            f.write_char(',')?;
+          }
+          write!(f, "{}", value)?;
+        }
+        f.write_char(']')
+      }
+      Json::Object(ref members) => {
+        f.write_char('{')?;
+        for (i, (key, value)) in members.iter().enumerate() {
+          if i > 0 {
+            f.write_char(',')?;
+          }
+          write!(f, "{}:{}", Json::String(key.clone()), value)?;
+        }
+        f.write_char('}')
+      }
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::Json;
+
+  #[test]
+  fn compact() {
+    let value = Json::object(vec![("a", Json::numbers(&[1.0, 0.5, f64::NAN])),
+                                  ("b", Json::from("x\"y\n</script>")),
+                                  ("c", Json::Null)]);
+    assert_eq!(value.to_string(),
+               r#"{"a":[1,0.5,null],"b":"x\"y\n\u003c/script>","c":null}"#);
+  }
+
+  #[test]
+  fn pretty() {
+    let value = Json::object(vec![("values",
+                                   Json::Array(vec![Json::object(vec![("x", Json::from(1.0))]),
+                                                    Json::object(vec![("x", Json::from(2.0))])])),
+                                  ("flag", Json::from(true))]);
+    assert_eq!(value.pretty(),
+               "{\n  \"values\": [\n    {\"x\":1},\n    {\"x\":2}\n  ],\n  \"flag\": true\n}\n");
+  }
+}
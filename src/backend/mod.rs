@@ -1,26 +1,74 @@
+#[macro_use]
+mod scene;
+mod gnuplot;
+mod html;
+mod json;
 mod mpl;
+mod mpl_script;
+mod pgfplots;
+mod pycode;
+mod recorder;
+#[cfg(feature = "svg")]
+mod svg;
+mod terminal;
+mod vega;
 #[cfg(feature = "native")]
 mod mpl_native;
 
-use std::io;
+use axes2d::{Bins, Deviation, Levels, Scale};
+use error::Result;
+use savefig::SaveOptions;
 
-pub use self::mpl::Matplotlib;
+pub use self::gnuplot::Gnuplot;
+pub use self::html::Html;
+pub use self::json::Json;
+pub use self::mpl::{Matplotlib, MatplotlibBuilder};
+pub use self::mpl_script::MatplotlibScript;
+pub use self::pgfplots::Pgfplots;
+pub use self::recorder::{Call, OwnedBins, OwnedDeviation, OwnedLevels, Recorder};
+pub use self::terminal::{Charset, Terminal};
+pub use self::vega::VegaLite;
 #[cfg(feature = "native")]
 pub use self::mpl_native::MatplotlibNative;
+#[cfg(feature = "svg")]
+pub use self::svg::Svg;
 
 
 pub trait Backend {
-  fn figure(&mut self) -> io::Result<&mut Self>;
-  fn subplot(&mut self, rows: u32, cols: u32, n: u32) -> io::Result<&mut Self>;
-  fn xlabel(&mut self, xlabel: &str) -> io::Result<&mut Self>;
-  fn ylabel(&mut self, ylabel: &str) -> io::Result<&mut Self>;
-  fn grid(&mut self, grid: bool) -> io::Result<&mut Self>;
-  fn legend(&mut self, loc: &str) -> io::Result<&mut Self>;
-  fn xlim(&mut self, xlim: &(f64, f64)) -> io::Result<&mut Self>;
-  fn ylim(&mut self, ylim: &(f64, f64)) -> io::Result<&mut Self>;
-  fn set_style(&mut self, stylename: &str) -> io::Result<&mut Self>;
-  fn savefig(&mut self, filename: &str) -> io::Result<&mut Self>;
-  fn show(&mut self) -> io::Result<&mut Self>;
+  fn figure(&mut self,
+            figsize: &Option<(f64, f64)>,
+            dpi: &Option<f64>,
+            facecolor: &Option<String>,
+            edgecolor: &Option<String>)
+            -> Result<&mut Self>;
+  fn subplot(&mut self, rows: u32, cols: u32, n: u32) -> Result<&mut Self>;
+  fn suptitle(&mut self, suptitle: &str) -> Result<&mut Self>;
+  fn title(&mut self,
+           title: &str,
+           loc: &Option<String>,
+           fontsize: &Option<f64>)
+           -> Result<&mut Self>;
+  fn xlabel(&mut self, xlabel: &str) -> Result<&mut Self>;
+  fn ylabel(&mut self, ylabel: &str) -> Result<&mut Self>;
+  fn grid(&mut self, grid: bool) -> Result<&mut Self>;
+  fn legend(&mut self, loc: &str) -> Result<&mut Self>;
+  fn xlim(&mut self, xlim: &(f64, f64)) -> Result<&mut Self>;
+  fn ylim(&mut self, ylim: &(f64, f64)) -> Result<&mut Self>;
+  fn colorbar(&mut self,
+              label: &Option<String>,
+              orientation: &Option<String>,
+              ticks: &Option<&[f64]>,
+              extend: &Option<String>)
+              -> Result<&mut Self>;
+  fn xscale(&mut self, scale: &Scale) -> Result<&mut Self>;
+  fn yscale(&mut self, scale: &Scale) -> Result<&mut Self>;
+  fn set_style(&mut self, stylename: &str) -> Result<&mut Self>;
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self>;
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self>;
+  /// render the current figure into memory, in the format given by `options` (PNG by default).
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>>;
+  fn show(&mut self) -> Result<&mut Self>;
+  #[allow(clippy::too_many_arguments)]
   fn plot(&mut self,
           xdata: &[f64],
           ydata: &[f64],
@@ -29,21 +77,88 @@ pub trait Backend {
           marker: &Option<String>,
           linestyle: &Option<String>,
           linewidth: &Option<f64>)
-          -> io::Result<&mut Self>;
+          -> Result<&mut Self>;
   fn scatter(&mut self,
              xdata: &[f64],
              ydata: &[f64],
              label: &Option<String>,
              color: &Option<String>,
              marker: &Option<String>)
-             -> io::Result<&mut Self>;
+             -> Result<&mut Self>;
+  #[allow(clippy::too_many_arguments)]
   fn fill_between(&mut self,
                   x: &[f64],
                   y1: &[f64],
                   y2: &[f64],
                   where_: &Option<&[bool]>,
                   interpolate: bool,
-                  step: &Option<String>)
-                  -> io::Result<&mut Self>;
-  fn tight_layout(&mut self) -> io::Result<&mut Self>;
+                  step: &Option<String>,
+                  label: &Option<String>)
+                  -> Result<&mut Self>;
+  #[allow(clippy::too_many_arguments)]
+  fn bar(&mut self,
+         x: &[f64],
+         height: &[f64],
+         width: &Option<f64>,
+         bottom: &Option<&[f64]>,
+         horizontal: bool,
+         label: &Option<String>,
+         color: &Option<String>,
+         edgecolor: &Option<String>,
+         align: &Option<String>)
+         -> Result<&mut Self>;
+  #[allow(clippy::too_many_arguments)]
+  fn hist(&mut self,
+          data: &[&[f64]],
+          bins: &Option<Bins>,
+          weights: &Option<&[&[f64]]>,
+          density: bool,
+          cumulative: bool,
+          histtype: &Option<String>,
+          label: &Option<String>,
+          color: &Option<String>)
+          -> Result<&mut Self>;
+  #[allow(clippy::too_many_arguments)]
+  fn errorbar(&mut self,
+              xdata: &[f64],
+              ydata: &[f64],
+              xerr: &Option<Deviation>,
+              yerr: &Option<Deviation>,
+              capsize: &Option<f64>,
+              ecolor: &Option<String>,
+              label: &Option<String>,
+              color: &Option<String>,
+              marker: &Option<String>,
+              linestyle: &Option<String>,
+              linewidth: &Option<f64>)
+              -> Result<&mut Self>;
+  #[allow(clippy::too_many_arguments)]
+  fn imshow(&mut self,
+            data: &[f64],
+            shape: (usize, usize),
+            cmap: &Option<String>,
+            vmin: &Option<f64>,
+            vmax: &Option<f64>,
+            interpolation: &Option<String>,
+            origin: &Option<String>,
+            extent: &Option<(f64, f64, f64, f64)>)
+            -> Result<&mut Self>;
+  #[allow(clippy::too_many_arguments)]
+  fn contour(&mut self,
+             x: &[f64],
+             y: &[f64],
+             z: &[f64],
+             levels: &Option<Levels>,
+             cmap: &Option<String>,
+             linewidths: &Option<f64>,
+             clabel: bool)
+             -> Result<&mut Self>;
+  fn contourf(&mut self,
+              x: &[f64],
+              y: &[f64],
+              z: &[f64],
+              levels: &Option<Levels>,
+              cmap: &Option<String>)
+              -> Result<&mut Self>;
+  fn tight_layout(&mut self) -> Result<&mut Self>;
 }
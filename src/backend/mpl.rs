@@ -1,93 +1,305 @@
-use std::io::{self, Write};
-use std::process::{Command, Child, Stdio};
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Child, ChildStdout, Stdio};
+use axes2d::{Bins, Deviation, Levels, Scale};
 use backend::Backend;
+use backend::pycode::{self, Arrays, PRELUDE};
+use error::{Error, Result};
+use savefig::SaveOptions;
+
+/// Python script which runs in the child process.
+///
+/// It reads length-prefixed chunks of script from stdin and executes them in order.
+/// After each chunk, a response is written back to stdout:
+///
+/// * `ok <len>\n<payload>` - the payload is the bytes stored into `__rustplotlib_output__`
+///   by the chunk, or empty.
+/// * `err <len>\n<payload>` - the payload is the exception type, the message and the
+///   traceback, separated by NUL characters.
+///
+/// `sys.stdout` is redirected to stderr so that the output of `print()` does not corrupt
+/// the channel.
+const DRIVER: &str = r#"
+import sys, traceback
+_in = getattr(sys.stdin, 'buffer', sys.stdin)
+_out = getattr(sys.stdout, 'buffer', sys.stdout)
+sys.stdout = sys.stderr
+_ns = {'__name__': '__main__'}
+while True:
+    _header = _in.readline()
+    if not _header:
+        break
+    _code = _in.read(int(_header)).decode('utf-8')
+    try:
+        exec(compile(_code, '<rustplotlib>', 'exec'), _ns)
+        _status, _data = 'ok', _ns.pop('__rustplotlib_output__', b'')
+    except Exception as e:
+        _ns.pop('__rustplotlib_output__', None)
+        _status, _data = 'err', u'\0'.join([type(e).__name__, u'%s' % e,
+                                             traceback.format_exc()]).encode('utf-8')
+    _out.write(('%s %d\n' % (_status, len(_data))).encode('ascii'))
+    _out.write(_data)
+    _out.flush()
+"#;
 
 /// Represents an instance of Python process which executes operations.
+///
+/// Each operation waits for the response of the Python process, and an exception raised
+/// in Python is returned as `Error::Python`.
 pub struct Matplotlib {
   child: Child,
+  stdout: BufReader<ChildStdout>,
 }
 
-impl Matplotlib {
-  /// create an instance of Matplotlib backend.
-  pub fn new() -> io::Result<Matplotlib> {
-    let child = Command::new("python").arg("-")
+/// Builder of `Matplotlib`, to configure the Python process.
+#[derive(Debug, Default, Clone)]
+pub struct MatplotlibBuilder {
+  python: Option<OsString>,
+  envs: Vec<(OsString, OsString)>,
+  current_dir: Option<PathBuf>,
+  virtualenv: Option<PathBuf>,
+}
+
+impl MatplotlibBuilder {
+  /// set the path of Python interpreter.
+  ///
+  /// if not set, the interpreter in the virtualenv or `python` in `PATH` is used.
+  pub fn python<S: AsRef<OsStr>>(mut self, python: S) -> Self {
+    self.python = Some(python.as_ref().to_owned());
+    self
+  }
+
+  /// add an environment variable of the Python process (e.g. `MPLBACKEND`, `PYTHONPATH`).
+  pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> Self {
+    self.envs.push((key.as_ref().to_owned(), value.as_ref().to_owned()));
+    self
+  }
+
+  /// set the working directory of the Python process.
+  pub fn current_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+    self.current_dir = Some(dir.as_ref().to_owned());
+    self
+  }
+
+  /// run the Python process in the virtualenv located at `dir`.
+  pub fn virtualenv<P: AsRef<Path>>(mut self, dir: P) -> Self {
+    self.virtualenv = Some(dir.as_ref().to_owned());
+    self
+  }
+
+  /// launch the Python process, and check that matplotlib can be imported.
+  pub fn spawn(self) -> Result<Matplotlib> {
+    let python = self.interpreter();
+    let mut command = Command::new(&python);
+    command.arg("-c")
+      .arg(DRIVER)
       .stdin(Stdio::piped())
-      .stdout(Stdio::inherit())
-      .stderr(Stdio::inherit())
-      .spawn()?;
+      .stdout(Stdio::piped())
+      .stderr(Stdio::inherit());
+    if let Some(ref venv) = self.virtualenv {
+      let mut paths = vec![venv.join(VENV_BIN)];
+      paths.extend(env::var_os("PATH").iter().flat_map(env::split_paths));
+      command.env("VIRTUAL_ENV", venv)
+        .env("PATH", env::join_paths(paths).map_err(|e| Error::NotAvailable(e.to_string()))?)
+        .env_remove("PYTHONHOME");
+    }
+    command.envs(self.envs.iter().cloned());
+    if let Some(ref dir) = self.current_dir {
+      command.current_dir(dir);
+    }
 
-    let mut mpl = Matplotlib { child: child };
-    mpl.exec("import matplotlib.pyplot as plt")?;
+    let python = python.to_string_lossy().into_owned();
+    let mut child = command.spawn()
+      .map_err(|e| {
+        Error::NotAvailable(format!("failed to launch the Python interpreter `{}`: {}", python, e))
+      })?;
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+
+    let mut mpl = Matplotlib { child, stdout };
+    mpl.exec(PRELUDE)
+      .map_err(|e| {
+        let module = match e {
+          Error::Python { ref message, .. } => missing_module(message),
+          _ => None,
+        };
+        let module = module.unwrap_or("matplotlib or numpy");
+        Error::NotAvailable(format!("{} is not available in `{}`: {}", module, python, e))
+      })?;
     Ok(mpl)
   }
 
+  fn interpreter(&self) -> OsString {
+    match (self.python.as_ref(), self.virtualenv.as_ref()) {
+      (Some(python), _) => python.clone(),
+      (None, Some(venv)) => venv.join(VENV_BIN).join("python").into_os_string(),
+      (None, None) => "python".into(),
+    }
+  }
+}
+
+#[cfg(windows)]
+const VENV_BIN: &str = "Scripts";
+#[cfg(not(windows))]
+const VENV_BIN: &str = "bin";
+
+impl Matplotlib {
+  /// create an instance of Matplotlib backend, with `python` in `PATH`.
+  pub fn new() -> Result<Matplotlib> {
+    Matplotlib::builder().spawn()
+  }
+
+  /// create a builder to configure the Python process.
+  pub fn builder() -> MatplotlibBuilder {
+    MatplotlibBuilder::default()
+  }
+
   /// wait until all operations are finished.
-  pub fn wait(&mut self) -> io::Result<()> {
-    self.child.wait().and(Ok(()))
+  pub fn wait(&mut self) -> Result<()> {
+    self.child.wait()?;
+    Ok(())
   }
 
   /// execute a string as Python script.
-  pub fn exec<S: AsRef<str>>(&mut self, script: S) -> io::Result<&mut Self> {
-    {
-      let ref mut stdin = self.child.stdin.as_mut().unwrap();
-      stdin.write_all(script.as_ref().as_bytes())?;
-      stdin.write_all(b"\n")?;
-    }
+  ///
+  /// the output of `print()` in the script is written to stderr.
+  pub fn exec<S: AsRef<str>>(&mut self, script: S) -> Result<&mut Self> {
+    self.request(script)?;
     Ok(self)
   }
 
+  /// execute a string as Python script, and receive the bytes stored into
+  /// `__rustplotlib_output__` by the script.
+  pub fn request<S: AsRef<str>>(&mut self, script: S) -> Result<Vec<u8>> {
+    self.send(script.as_ref())?;
+    self.receive()
+  }
+
+  fn send(&mut self, script: &str) -> Result<()> {
+    let stdin = self.child.stdin.as_mut().unwrap();
+    stdin.write_all(format!("{}\n", script.len()).as_bytes())?;
+    stdin.write_all(script.as_bytes())?;
+    stdin.flush()?;
+    Ok(())
+  }
+
+  fn receive(&mut self) -> Result<Vec<u8>> {
+    let mut header = String::new();
+    if self.stdout.read_line(&mut header)? == 0 {
+      return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                "the Python process exited unexpectedly")
+        .into());
+    }
+    let (status, len) = parse_header(&header)
+      .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid response header"))?;
+    let mut data = vec![0; len];
+    self.stdout.read_exact(&mut data)?;
+
+    match status {
+      "ok" => Ok(data),
+      _ => {
+        let payload = String::from_utf8_lossy(&data);
+        let mut fields = payload.splitn(3, '\0');
+        Err(Error::Python {
+          exception: fields.next().unwrap_or("").to_owned(),
+          message: fields.next().unwrap_or("").to_owned(),
+          traceback: fields.next().map(ToOwned::to_owned),
+        })
+      }
+    }
+  }
+
   // save current figure as a pickle-format file.
-  pub fn dump_pickle<S: AsRef<str>>(&mut self, filename: S) -> io::Result<&mut Self> {
-    self.exec("import numpy as np")?;
-    self.exec("import pickle as pl")?;
-    self.exec(format!("pl.dump(plt.gcf(), open('{}', 'wb'))", filename.as_ref()))?;
-    Ok(self)
+  pub fn dump_pickle<S: AsRef<str>>(&mut self, filename: S) -> Result<&mut Self> {
+    self.exec(pycode::dump_pickle(filename.as_ref()))
   }
 }
-
 impl Backend for Matplotlib {
-  fn figure(&mut self) -> io::Result<&mut Self> {
-    self.exec("plt.figure()")
+  fn figure(&mut self,
+            figsize: &Option<(f64, f64)>,
+            dpi: &Option<f64>,
+            facecolor: &Option<String>,
+            edgecolor: &Option<String>)
+            -> Result<&mut Self> {
+    self.exec(pycode::figure(figsize, dpi, facecolor, edgecolor))
+  }
+
+  fn subplot(&mut self, rows: u32, cols: u32, n: u32) -> Result<&mut Self> {
+    self.exec(pycode::subplot(rows, cols, n))
+  }
+
+  fn suptitle(&mut self, suptitle: &str) -> Result<&mut Self> {
+    self.exec(pycode::suptitle(suptitle))
   }
 
-  fn subplot(&mut self, rows: u32, cols: u32, n: u32) -> io::Result<&mut Self> {
-    self.exec(format!("plt.subplot({}, {}, {})", rows, cols, n))
+  fn title(&mut self,
+           title: &str,
+           loc: &Option<String>,
+           fontsize: &Option<f64>)
+           -> Result<&mut Self> {
+    self.exec(pycode::title(title, loc, fontsize))
   }
 
-  fn xlabel(&mut self, xlabel: &str) -> io::Result<&mut Self> {
-    self.exec(format!("plt.xlabel('{}')", xlabel))
+  fn xlabel(&mut self, xlabel: &str) -> Result<&mut Self> {
+    self.exec(pycode::xlabel(xlabel))
   }
 
-  fn ylabel(&mut self, ylabel: &str) -> io::Result<&mut Self> {
-    self.exec(format!("plt.ylabel('{}')", ylabel))
+  fn ylabel(&mut self, ylabel: &str) -> Result<&mut Self> {
+    self.exec(pycode::ylabel(ylabel))
   }
 
-  fn grid(&mut self, grid: bool) -> io::Result<&mut Self> {
-    self.exec(format!("plt.grid({})", if grid { "True" } else { "False" }))
+  fn grid(&mut self, grid: bool) -> Result<&mut Self> {
+    self.exec(pycode::grid(grid))
   }
 
-  fn legend(&mut self, loc: &str) -> io::Result<&mut Self> {
-    self.exec(format!("plt.legend(loc='{}')", loc))
+  fn legend(&mut self, loc: &str) -> Result<&mut Self> {
+    self.exec(pycode::legend(loc))
   }
 
-  fn xlim(&mut self, xlim: &(f64, f64)) -> io::Result<&mut Self> {
-    self.exec(format!("plt.xlim(({}, {}))", xlim.0, xlim.1))
+  fn xlim(&mut self, xlim: &(f64, f64)) -> Result<&mut Self> {
+    self.exec(pycode::xlim(xlim))
   }
 
-  fn ylim(&mut self, ylim: &(f64, f64)) -> io::Result<&mut Self> {
-    self.exec(format!("plt.ylim(({}, {}))", ylim.0, ylim.1))
+  fn ylim(&mut self, ylim: &(f64, f64)) -> Result<&mut Self> {
+    self.exec(pycode::ylim(ylim))
   }
 
-  fn set_style(&mut self, stylename: &str) -> io::Result<&mut Self> {
-    self.exec(format!("plt.style.use('{}')", stylename))
+  fn xscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.exec(pycode::xscale(scale))
   }
 
-  fn savefig(&mut self, filename: &str) -> io::Result<&mut Self> {
-    self.exec(format!("plt.savefig('{}')", filename))
+  fn yscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.exec(pycode::yscale(scale))
   }
 
-  fn show(&mut self) -> io::Result<&mut Self> {
-    self.exec("plt.show()")
+  fn colorbar(&mut self,
+              label: &Option<String>,
+              orientation: &Option<String>,
+              ticks: &Option<&[f64]>,
+              extend: &Option<String>)
+              -> Result<&mut Self> {
+    self.exec(pycode::colorbar(label, orientation, ticks, extend))
+  }
+
+  fn set_style(&mut self, stylename: &str) -> Result<&mut Self> {
+    self.exec(pycode::set_style(stylename))
+  }
+
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.exec(pycode::savefig(filename))
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    self.exec(pycode::savefig_with(filename, options))
+  }
+
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    self.request(pycode::savefig_bytes(options))
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    self.exec(pycode::show())
   }
 
   fn scatter(&mut self,
@@ -96,19 +308,8 @@ impl Backend for Matplotlib {
              label: &Option<String>,
              color: &Option<String>,
              marker: &Option<String>)
-             -> io::Result<&mut Self> {
-    let mut code = format!("plt.scatter({}, {}, ", to_pyvec(xdata), to_pyvec(ydata));
-    if let &Some(ref label) = label {
-      code += &format!("label='{}', ", label);
-    }
-    if let &Some(ref color) = color {
-      code += &format!("color='{}', ", color);
-    }
-    if let &Some(ref marker) = marker {
-      code += &format!("marker='{}', ", marker);
-    }
-    code += ")";
-    self.exec(code)
+             -> Result<&mut Self> {
+    self.exec(pycode::scatter(Arrays::Binary, xdata, ydata, label, color, marker))
   }
 
   fn plot(&mut self,
@@ -119,25 +320,8 @@ impl Backend for Matplotlib {
           marker: &Option<String>,
           linestyle: &Option<String>,
           linewidth: &Option<f64>)
-          -> io::Result<&mut Self> {
-    let mut code = format!("plt.plot({}, {}, ", to_pyvec(xdata), to_pyvec(ydata));
-    if let &Some(ref label) = label {
-      code += &format!("label='{}', ", label);
-    }
-    if let &Some(ref color) = color {
-      code += &format!("color='{}', ", color);
-    }
-    if let &Some(ref marker) = marker {
-      code += &format!("marker='{}', ", marker);
-    }
-    if let &Some(ref ls) = linestyle {
-      code += &format!("linestyle='{}', ", ls);
-    }
-    if let &Some(ref lw) = linewidth {
-      code += &format!("linewidth='{}', ", lw);
-    }
-    code += ")";
-    self.exec(code)
+          -> Result<&mut Self> {
+    self.exec(pycode::plot(Arrays::Binary, xdata, ydata, label, color, marker, linestyle, linewidth))
   }
 
   fn fill_between(&mut self,
@@ -146,25 +330,92 @@ impl Backend for Matplotlib {
                   y2: &[f64],
                   where_: &Option<&[bool]>,
                   interpolate: bool,
-                  step: &Option<String>)
-                  -> io::Result<&mut Self> {
-    let mut code = format!("plt.fill_between({}, {}, {}, ",
-                           to_pyvec(x),
-                           to_pyvec(y1),
-                           to_pyvec(y2));
-    if let &Some(ref where_) = where_ {
-      code += &format!("where='{}', ", to_pyvec(where_));
-    }
-    code += &format!("interpolate={}, ", interpolate.to_pystr());
-    if let &Some(ref step) = step {
-      code += &format!("step='{}', ", step);
-    }
-    code += ")";
-    self.exec(code)
+                  step: &Option<String>,
+                  label: &Option<String>)
+                  -> Result<&mut Self> {
+    self.exec(pycode::fill_between(Arrays::Binary, x, y1, y2, where_, interpolate, step, label))
   }
 
-  fn tight_layout(&mut self) -> io::Result<&mut Self> {
-    self.exec("plt.tight_layout()")
+  fn bar(&mut self,
+         x: &[f64],
+         height: &[f64],
+         width: &Option<f64>,
+         bottom: &Option<&[f64]>,
+         horizontal: bool,
+         label: &Option<String>,
+         color: &Option<String>,
+         edgecolor: &Option<String>,
+         align: &Option<String>)
+         -> Result<&mut Self> {
+    self.exec(pycode::bar(Arrays::Binary, x, height, width, bottom, horizontal, label, color, edgecolor, align))
+  }
+
+  fn hist(&mut self,
+          data: &[&[f64]],
+          bins: &Option<Bins>,
+          weights: &Option<&[&[f64]]>,
+          density: bool,
+          cumulative: bool,
+          histtype: &Option<String>,
+          label: &Option<String>,
+          color: &Option<String>)
+          -> Result<&mut Self> {
+    self.exec(pycode::hist(Arrays::Binary, data, bins, weights, density, cumulative, histtype, label, color))
+  }
+
+  fn errorbar(&mut self,
+              xdata: &[f64],
+              ydata: &[f64],
+              xerr: &Option<Deviation>,
+              yerr: &Option<Deviation>,
+              capsize: &Option<f64>,
+              ecolor: &Option<String>,
+              label: &Option<String>,
+              color: &Option<String>,
+              marker: &Option<String>,
+              linestyle: &Option<String>,
+              linewidth: &Option<f64>)
+              -> Result<&mut Self> {
+    self.exec(pycode::errorbar(Arrays::Binary, xdata, ydata, xerr, yerr, capsize, ecolor, label, color, marker, linestyle, linewidth))
+  }
+
+  fn imshow(&mut self,
+            data: &[f64],
+            shape: (usize, usize),
+            cmap: &Option<String>,
+            vmin: &Option<f64>,
+            vmax: &Option<f64>,
+            interpolation: &Option<String>,
+            origin: &Option<String>,
+            extent: &Option<(f64, f64, f64, f64)>)
+            -> Result<&mut Self> {
+    self.exec(pycode::imshow(Arrays::Binary, data, shape, cmap, vmin, vmax, interpolation, origin, extent))
+  }
+
+  fn contour(&mut self,
+             x: &[f64],
+             y: &[f64],
+             z: &[f64],
+             levels: &Option<Levels>,
+             cmap: &Option<String>,
+             linewidths: &Option<f64>,
+             clabel: bool)
+             -> Result<&mut Self> {
+    self.exec(pycode::contour(Arrays::Binary, x, y, z, levels, cmap, linewidths, clabel))
+  }
+
+  fn contourf(&mut self,
+              x: &[f64],
+              y: &[f64],
+              z: &[f64],
+              levels: &Option<Levels>,
+              cmap: &Option<String>)
+              -> Result<&mut Self> {
+    self.exec(pycode::contourf(Arrays::Binary, x, y, z, levels, cmap))
+  }
+
+  fn tight_layout(&mut self) -> Result<&mut Self> {
+    self.exec(pycode::tight_layout())
   }
 }
 
@@ -174,25 +425,32 @@ impl Drop for Matplotlib {
   }
 }
 
-trait ToPyStr {
-  fn to_pystr(&self) -> String;
+fn parse_header(header: &str) -> Option<(&str, usize)> {
+  let mut parts = header.split_whitespace();
+  let status = match parts.next() {
+    Some(status @ "ok") | Some(status @ "err") => status,
+    _ => return None,
+  };
+  let len = parts.next().and_then(|len| len.parse().ok())?;
+  Some((status, len))
 }
-impl ToPyStr for f64 {
-  fn to_pystr(&self) -> String {
-    format!("{}", self)
-  }
-}
-impl ToPyStr for bool {
-  fn to_pystr(&self) -> String {
-    if *self {
-      "True".to_owned()
-    } else {
-      "False".to_owned()
-    }
-  }
+
+/// the name of module which failed to be imported, from the message of `ImportError`.
+fn missing_module(message: &str) -> Option<&str> {
+  // "No module named 'numpy'" in Python 3, and "No module named numpy" in Python 2.
+  let name = message.split("No module named ").nth(1)?;
+  Some(name.trim().trim_matches('\''))
 }
 
-fn to_pyvec<T: ToPyStr>(data: &[T]) -> String {
-  let data: Vec<String> = data.iter().map(|x| x.to_pystr()).collect();
-  format!("[{}]", data.join(","))
+
+#[cfg(test)]
+mod tests {
+  use super::missing_module;
+
+  #[test]
+  fn import_error() {
+    assert_eq!(missing_module("No module named 'numpy'"), Some("numpy"));
+    assert_eq!(missing_module("No module named matplotlib.pyplot"), Some("matplotlib.pyplot"));
+    assert_eq!(missing_module("cannot import name 'foo'"), None);
+  }
 }
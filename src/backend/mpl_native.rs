@@ -1,99 +1,189 @@
-use std::io;
+use axes2d::{Bins, Deviation, Levels, Scale};
 use backend::Backend;
-use cpython::{GILGuard, Python, PyModule, PyDict, NoArgs};
+use error::{Error, Result};
+use savefig::{Format, SaveOptions};
+use cpython::{GILGuard, Python, PyModule, PyDict, PyBytes, PyErr, PyResult, NoArgs,
+              ObjectProtocol};
 
 
+/// Represents the embedded Python interpreter which executes operations.
+///
+/// An exception raised in Python is returned as `Error::Python`.
 pub struct MatplotlibNative {
   gil: GILGuard,
   plt: PyModule,
 }
 
 impl MatplotlibNative {
-  pub fn new() -> MatplotlibNative {
+  /// create an instance of MatplotlibNative backend.
+  ///
+  /// this fails if matplotlib cannot be imported.
+  pub fn new() -> Result<MatplotlibNative> {
     let gil = Python::acquire_gil();
-    let plt;
-    {
+    let plt = {
       let py = gil.python();
-      plt = PyModule::import(py, "matplotlib.pyplot").unwrap();
-    }
-    MatplotlibNative {
-      gil: gil,
-      plt: plt,
-    }
+      PyModule::import(py, "matplotlib.pyplot").map_pyerr(py)?
+    };
+    Ok(MatplotlibNative { gil, plt })
   }
 
   pub fn py<'a>(&'a self) -> Python<'a> {
     self.gil.python()
   }
 
-  pub fn exec<S: AsRef<str>>(&mut self, script: S) -> io::Result<&mut Self> {
-    self.py().run(script.as_ref(), None, None).unwrap();
+  pub fn exec<S: AsRef<str>>(&mut self, script: S) -> Result<&mut Self> {
+    self.py().run(script.as_ref(), None, None).map_pyerr(self.py())?;
     Ok(self)
   }
 
   // save current figure as a pickle-format file.
-  pub fn dump_pickle<S: AsRef<str>>(&mut self, filename: S) -> io::Result<&mut Self> {
-    let pl = PyModule::import(self.py(), "pickle").unwrap();
-    let gcf = self.plt.call(self.py(), "gcf", NoArgs, None).unwrap();
-    let file = self.py().eval(&format!("open('{}', 'wb')", filename.as_ref()), None, None).unwrap();
-    pl.call(self.py(), "dump", (gcf, file), None).unwrap();
+  pub fn dump_pickle<S: AsRef<str>>(&mut self, filename: S) -> Result<&mut Self> {
+    let pl = PyModule::import(self.py(), "pickle").map_pyerr(self.py())?;
+    let gcf = self.plt.call(self.py(), "gcf", NoArgs, None).map_pyerr(self.py())?;
+    let file = PyModule::import(self.py(), "io")
+      .and_then(|io| io.call(self.py(), "open", (filename.as_ref(), "wb"), None))
+      .map_pyerr(self.py())?;
+    let result = pl.call(self.py(), "dump", (gcf, &file), None);
+    file.call_method(self.py(), "close", NoArgs, None).map_pyerr(self.py())?;
+    result.map_pyerr(self.py())?;
     Ok(self)
   }
 }
 
 impl Backend for MatplotlibNative {
   /// call `plt.figure()` to create a instance of `matplotlib.figure.Figure`.
-  fn figure(&mut self) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "figure", NoArgs, None).unwrap();
+  fn figure(&mut self,
+            figsize: &Option<(f64, f64)>,
+            dpi: &Option<f64>,
+            facecolor: &Option<String>,
+            edgecolor: &Option<String>)
+            -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    kwargs.set_item(self.py(), "figsize", figsize).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "dpi", dpi).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "facecolor", facecolor).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "edgecolor", edgecolor).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "figure", NoArgs, Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.plt.call(self.py(), "savefig", (filename,), None).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    let kwargs = save_kwargs(self.py(), options)?;
+    self.plt.call(self.py(), "savefig", (filename,), Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    let format = options.format.unwrap_or(Format::Png);
+    let kwargs = save_kwargs(self.py(), &options.clone().format(format))?;
+    let buf = PyModule::import(self.py(), "io")
+      .and_then(|io| io.call(self.py(), "BytesIO", NoArgs, None))
+      .map_pyerr(self.py())?;
+    self.plt.call(self.py(), "savefig", (&buf,), Some(&kwargs)).map_pyerr(self.py())?;
+    let data = buf.call_method(self.py(), "getvalue", NoArgs, None)
+      .and_then(|data| data.cast_into::<PyBytes>(self.py()).map_err(PyErr::from))
+      .map_pyerr(self.py())?;
+    Ok(data.data(self.py()).to_vec())
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    self.plt.call(self.py(), "show", NoArgs, None).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn subplot(&mut self, i: u32, j: u32, k: u32) -> Result<&mut Self> {
+    self.plt.call(self.py(), "subplot", (i, j, k), None).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn suptitle(&mut self, suptitle: &str) -> Result<&mut Self> {
+    self.plt.call(self.py(), "suptitle", (suptitle,), None).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn title(&mut self,
+           title: &str,
+           loc: &Option<String>,
+           fontsize: &Option<f64>)
+           -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    if let Some(ref loc) = *loc {
+      kwargs.set_item(self.py(), "loc", loc).map_pyerr(self.py())?;
+    }
+    kwargs.set_item(self.py(), "fontsize", fontsize).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "title", (title,), Some(&kwargs)).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn savefig(&mut self, filename: &str) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "savefig", (filename,), None).unwrap();
+  fn xlabel(&mut self, xlabel: &str) -> Result<&mut Self> {
+    self.plt.call(self.py(), "xlabel", (xlabel,), None).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn show(&mut self) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "show", NoArgs, None).unwrap();
+  fn ylabel(&mut self, ylabel: &str) -> Result<&mut Self> {
+    self.plt.call(self.py(), "ylabel", (ylabel,), None).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn subplot(&mut self, i: u32, j: u32, k: u32) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "subplot", (i, j, k), None).unwrap();
+  fn grid(&mut self, grid: bool) -> Result<&mut Self> {
+    self.plt.call(self.py(), "grid", (grid,), None).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn xlabel(&mut self, xlabel: &str) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "xlabel", (xlabel,), None).unwrap();
+  fn legend(&mut self, loc: &str) -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    kwargs.set_item(self.py(), "loc", loc).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "legend", NoArgs, Some(&kwargs)).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn ylabel(&mut self, ylabel: &str) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "ylabel", (ylabel,), None).unwrap();
+  fn xlim(&mut self, xlim: &(f64, f64)) -> Result<&mut Self> {
+    self.plt.call(self.py(), "xlim", xlim, None).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn grid(&mut self, grid: bool) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "grid", (grid,), None).unwrap();
+  fn ylim(&mut self, ylim: &(f64, f64)) -> Result<&mut Self> {
+    self.plt.call(self.py(), "ylim", ylim, None).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn legend(&mut self, loc: &str) -> io::Result<&mut Self> {
+  fn xscale(&mut self, scale: &Scale) -> Result<&mut Self> {
     let kwargs = PyDict::new(self.py());
-    kwargs.set_item(self.py(), "loc", loc).unwrap();
-    self.plt
-      .call(self.py(), "legend", NoArgs, Some(&kwargs))
-      .unwrap();
+    let name = scale_kwargs(self.py(), &kwargs, scale)?;
+    self.plt.call(self.py(), "xscale", (name,), Some(&kwargs)).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn xlim(&mut self, xlim: &(f64, f64)) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "xlim", xlim, None).unwrap();
+  fn yscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    let name = scale_kwargs(self.py(), &kwargs, scale)?;
+    self.plt.call(self.py(), "yscale", (name,), Some(&kwargs)).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn ylim(&mut self, ylim: &(f64, f64)) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "ylim", ylim, None).unwrap();
+  fn colorbar(&mut self,
+              label: &Option<String>,
+              orientation: &Option<String>,
+              ticks: &Option<&[f64]>,
+              extend: &Option<String>)
+              -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    if let Some(ref label) = *label {
+      kwargs.set_item(self.py(), "label", label).map_pyerr(self.py())?;
+    }
+    if let Some(ref orientation) = *orientation {
+      kwargs.set_item(self.py(), "orientation", orientation).map_pyerr(self.py())?;
+    }
+    kwargs.set_item(self.py(), "ticks", ticks).map_pyerr(self.py())?;
+    if let Some(ref extend) = *extend {
+      kwargs.set_item(self.py(), "extend", extend).map_pyerr(self.py())?;
+    }
+    self.plt.call(self.py(), "colorbar", NoArgs, Some(&kwargs)).map_pyerr(self.py())?;
     Ok(self)
   }
 
@@ -103,12 +193,12 @@ impl Backend for MatplotlibNative {
              label: &Option<String>,
              color: &Option<String>,
              marker: &Option<String>)
-             -> io::Result<&mut Self> {
+             -> Result<&mut Self> {
     let kwargs = PyDict::new(self.py());
-    kwargs.set_item(self.py(), "label", label).unwrap();
-    kwargs.set_item(self.py(), "color", color).unwrap();
-    kwargs.set_item(self.py(), "marker", marker).unwrap();
-    self.plt.call(self.py(), "scatter", (xdata, ydata), Some(&kwargs)).unwrap();
+    kwargs.set_item(self.py(), "label", label).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "color", color).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "marker", marker).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "scatter", (xdata, ydata), Some(&kwargs)).map_pyerr(self.py())?;
     Ok(self)
   }
 
@@ -120,14 +210,14 @@ impl Backend for MatplotlibNative {
           marker: &Option<String>,
           linestyle: &Option<String>,
           linewidth: &Option<f64>)
-          -> io::Result<&mut Self> {
+          -> Result<&mut Self> {
     let kwargs = PyDict::new(self.py());
-    kwargs.set_item(self.py(), "label", label).unwrap();
-    kwargs.set_item(self.py(), "color", color).unwrap();
-    kwargs.set_item(self.py(), "marker", marker).unwrap();
-    kwargs.set_item(self.py(), "ls", linestyle).unwrap();
-    kwargs.set_item(self.py(), "lw", linewidth).unwrap();
-    self.plt.call(self.py(), "plot", (xdata, ydata), Some(&kwargs)).unwrap();
+    kwargs.set_item(self.py(), "label", label).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "color", color).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "marker", marker).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "ls", linestyle).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "lw", linewidth).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "plot", (xdata, ydata), Some(&kwargs)).map_pyerr(self.py())?;
     Ok(self)
   }
 
@@ -137,28 +227,272 @@ impl Backend for MatplotlibNative {
                   y2: &[f64],
                   where_: &Option<&[bool]>,
                   interpolate: bool,
-                  step: &Option<String>)
-                  -> io::Result<&mut Self> {
+                  step: &Option<String>,
+                  label: &Option<String>)
+                  -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    kwargs.set_item(self.py(), "where", where_).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "interpolate", interpolate).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "step", step).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "label", label).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "fill_between", (x, y1, y2), Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn bar(&mut self,
+         x: &[f64],
+         height: &[f64],
+         width: &Option<f64>,
+         bottom: &Option<&[f64]>,
+         horizontal: bool,
+         label: &Option<String>,
+         color: &Option<String>,
+         edgecolor: &Option<String>,
+         align: &Option<String>)
+         -> Result<&mut Self> {
+    // `plt.barh()` takes the thickness as `height` and the base as `left`.
+    let (func, width_key, bottom_key) = if horizontal {
+      ("barh", "height", "left")
+    } else {
+      ("bar", "width", "bottom")
+    };
+    let kwargs = PyDict::new(self.py());
+    if let Some(ref width) = *width {
+      kwargs.set_item(self.py(), width_key, width).map_pyerr(self.py())?;
+    }
+    if let Some(bottom) = *bottom {
+      kwargs.set_item(self.py(), bottom_key, bottom).map_pyerr(self.py())?;
+    }
+    kwargs.set_item(self.py(), "label", label).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "color", color).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "edgecolor", edgecolor).map_pyerr(self.py())?;
+    if let Some(ref align) = *align {
+      kwargs.set_item(self.py(), "align", align).map_pyerr(self.py())?;
+    }
+    self.plt.call(self.py(), func, (x, height), Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn hist(&mut self,
+          data: &[&[f64]],
+          bins: &Option<Bins>,
+          weights: &Option<&[&[f64]]>,
+          density: bool,
+          cumulative: bool,
+          histtype: &Option<String>,
+          label: &Option<String>,
+          color: &Option<String>)
+          -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    match *bins {
+      Some(Bins::Count(n)) => kwargs.set_item(self.py(), "bins", n).map_pyerr(self.py())?,
+      Some(Bins::Edges(edges)) => kwargs.set_item(self.py(), "bins", edges).map_pyerr(self.py())?,
+      None => (),
+    }
+    kwargs.set_item(self.py(), "weights", weights).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "density", density).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "cumulative", cumulative).map_pyerr(self.py())?;
+    if let Some(ref histtype) = *histtype {
+      kwargs.set_item(self.py(), "histtype", histtype).map_pyerr(self.py())?;
+    }
+    kwargs.set_item(self.py(), "label", label).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "color", color).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "hist", (data,), Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn errorbar(&mut self,
+              xdata: &[f64],
+              ydata: &[f64],
+              xerr: &Option<Deviation>,
+              yerr: &Option<Deviation>,
+              capsize: &Option<f64>,
+              ecolor: &Option<String>,
+              label: &Option<String>,
+              color: &Option<String>,
+              marker: &Option<String>,
+              linestyle: &Option<String>,
+              linewidth: &Option<f64>)
+              -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    for &(key, err) in &[("xerr", xerr), ("yerr", yerr)] {
+      match *err {
+        Some(Deviation::Symmetric(err)) => kwargs.set_item(self.py(), key, err).map_pyerr(self.py())?,
+        Some(Deviation::Asymmetric(lower, upper)) => {
+          kwargs.set_item(self.py(), key, vec![lower, upper]).map_pyerr(self.py())?
+        }
+        None => (),
+      }
+    }
+    kwargs.set_item(self.py(), "capsize", capsize).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "ecolor", ecolor).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "label", label).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "color", color).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "marker", marker).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "ls", linestyle).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "lw", linewidth).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "errorbar", (xdata, ydata), Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn imshow(&mut self,
+            data: &[f64],
+            shape: (usize, usize),
+            cmap: &Option<String>,
+            vmin: &Option<f64>,
+            vmax: &Option<f64>,
+            interpolation: &Option<String>,
+            origin: &Option<String>,
+            extent: &Option<(f64, f64, f64, f64)>)
+            -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    kwargs.set_item(self.py(), "cmap", cmap).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "vmin", vmin).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "vmax", vmax).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "interpolation", interpolation).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "origin", origin).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "extent", extent).map_pyerr(self.py())?;
+    self.plt.call(self.py(), "imshow", (to_rows(data, shape),), Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn contour(&mut self,
+             x: &[f64],
+             y: &[f64],
+             z: &[f64],
+             levels: &Option<Levels>,
+             cmap: &Option<String>,
+             linewidths: &Option<f64>,
+             clabel: bool)
+             -> Result<&mut Self> {
     let kwargs = PyDict::new(self.py());
-    kwargs.set_item(self.py(), "where", where_).unwrap();
-    kwargs.set_item(self.py(), "interpolate", interpolate).unwrap();
-    kwargs.set_item(self.py(), "step", step).unwrap();
-    self.plt.call(self.py(), "fill_between", (x, y1, y2), Some(&kwargs)).unwrap();
+    set_levels(self.py(), &kwargs, levels)?;
+    kwargs.set_item(self.py(), "cmap", cmap).map_pyerr(self.py())?;
+    kwargs.set_item(self.py(), "linewidths", linewidths).map_pyerr(self.py())?;
+    let z = to_rows(z, (y.len(), x.len()));
+    let cs = self.plt.call(self.py(), "contour", (x, y, z), Some(&kwargs)).map_pyerr(self.py())?;
+    if clabel {
+      let kwargs = PyDict::new(self.py());
+      kwargs.set_item(self.py(), "inline", true).map_pyerr(self.py())?;
+      self.plt.call(self.py(), "clabel", (cs,), Some(&kwargs)).map_pyerr(self.py())?;
+    }
     Ok(self)
   }
 
-  fn set_style(&mut self, stylename: &str) -> io::Result<&mut Self> {
+  fn contourf(&mut self,
+              x: &[f64],
+              y: &[f64],
+              z: &[f64],
+              levels: &Option<Levels>,
+              cmap: &Option<String>)
+              -> Result<&mut Self> {
+    let kwargs = PyDict::new(self.py());
+    set_levels(self.py(), &kwargs, levels)?;
+    kwargs.set_item(self.py(), "cmap", cmap).map_pyerr(self.py())?;
+    let z = to_rows(z, (y.len(), x.len()));
+    self.plt.call(self.py(), "contourf", (x, y, z), Some(&kwargs)).map_pyerr(self.py())?;
+    Ok(self)
+  }
+
+  fn set_style(&mut self, stylename: &str) -> Result<&mut Self> {
     use cpython::FromPyObject;
     let style = self.plt
       .get(self.py(), "style")
       .and_then(|ref style| PyModule::extract(self.py(), style))
-      .unwrap();
-    style.call(self.py(), "use", (stylename,), None).unwrap();
+      .map_pyerr(self.py())?;
+    style.call(self.py(), "use", (stylename,), None).map_pyerr(self.py())?;
     Ok(self)
   }
 
-  fn tight_layout(&mut self) -> io::Result<&mut Self> {
-    self.plt.call(self.py(), "tight_layout", NoArgs, None).unwrap();
+  fn tight_layout(&mut self) -> Result<&mut Self> {
+    self.plt.call(self.py(), "tight_layout", NoArgs, None).map_pyerr(self.py())?;
     Ok(self)
   }
 }
+
+/// split a row-major 2D array into rows, which is converted into nested Python lists.
+fn to_rows(data: &[f64], (_, cols): (usize, usize)) -> Vec<&[f64]> {
+  data.chunks(cols.max(1)).collect()
+}
+
+fn set_levels(py: Python, kwargs: &PyDict, levels: &Option<Levels>) -> Result<()> {
+  match *levels {
+    Some(Levels::Count(n)) => kwargs.set_item(py, "levels", n).map_pyerr(py)?,
+    Some(Levels::Values(values)) => kwargs.set_item(py, "levels", values).map_pyerr(py)?,
+    None => (),
+  }
+  Ok(())
+}
+
+/// set the parameters of `scale` into `kwargs`, and return the name of scale.
+fn scale_kwargs(py: Python, kwargs: &PyDict, scale: &Scale) -> Result<&'static str> {
+  let name = match *scale {
+    Scale::Linear => "linear",
+    Scale::Log { base } => {
+      kwargs.set_item(py, "base", base).map_pyerr(py)?;
+      "log"
+    }
+    Scale::SymLog { linthresh } => {
+      kwargs.set_item(py, "linthresh", linthresh).map_pyerr(py)?;
+      "symlog"
+    }
+    Scale::Logit => "logit",
+  };
+  Ok(name)
+}
+
+fn save_kwargs(py: Python, options: &SaveOptions) -> Result<PyDict> {
+  let kwargs = PyDict::new(py);
+  kwargs.set_item(py, "dpi", options.dpi).map_pyerr(py)?;
+  if let Some(format) = options.format {
+    kwargs.set_item(py, "format", format.as_str()).map_pyerr(py)?;
+  }
+  kwargs.set_item(py, "transparent", options.transparent).map_pyerr(py)?;
+  if options.tight {
+    kwargs.set_item(py, "bbox_inches", "tight").map_pyerr(py)?;
+  }
+  if let Some(pad) = options.pad_inches {
+    kwargs.set_item(py, "pad_inches", pad).map_pyerr(py)?;
+  }
+  if !options.metadata.is_empty() {
+    kwargs.set_item(py, "metadata", &options.metadata).map_pyerr(py)?;
+  }
+  Ok(kwargs)
+}
+
+trait MapPyErr<T> {
+  /// convert the error of Python API into `Error::Python`.
+  fn map_pyerr(self, py: Python) -> Result<T>;
+}
+
+impl<T> MapPyErr<T> for PyResult<T> {
+  fn map_pyerr(self, py: Python) -> Result<T> {
+    self.map_err(|err| to_error(py, err))
+  }
+}
+
+fn to_error(py: Python, mut err: PyErr) -> Error {
+  err.normalize(py);
+  let exception = err.get_type(py).name(py).into_owned();
+  let message = err.pvalue
+    .as_ref()
+    .and_then(|value| value.str(py).ok())
+    .map(|message| message.to_string_lossy(py).into_owned())
+    .unwrap_or_default();
+  let traceback = format_traceback(py, &err);
+  Error::Python {
+    exception,
+    message,
+    traceback,
+  }
+}
+
+fn format_traceback(py: Python, err: &PyErr) -> Option<String> {
+  let value = err.pvalue.as_ref()?;
+  let traceback = err.ptraceback.as_ref()?;
+  let lines = PyModule::import(py, "traceback")
+    .and_then(|tb| tb.call(py, "format_exception", (&err.ptype, value, traceback), None))
+    .and_then(|lines| lines.extract::<Vec<String>>(py))
+    .ok()?;
+  Some(lines.concat())
+}
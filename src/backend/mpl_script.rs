@@ -0,0 +1,292 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use axes2d::{Bins, Deviation, Levels, Scale};
+use backend::Backend;
+use backend::pycode::{self, Arrays, PRELUDE};
+use error::{Error, Result};
+use savefig::SaveOptions;
+
+/// Records operations into a standalone Python script, instead of executing them.
+///
+/// The recorded script is same as the one `Matplotlib` executes, except that the data is
+/// written as list literals, so it can be read, run or tweaked later by `python` with
+/// matplotlib and numpy installed.
+#[derive(Debug, Clone)]
+pub struct MatplotlibScript {
+  script: String,
+}
+
+impl Default for MatplotlibScript {
+  fn default() -> MatplotlibScript {
+    MatplotlibScript::new()
+  }
+}
+
+impl MatplotlibScript {
+  /// create an empty script, which only contains the imports.
+  pub fn new() -> MatplotlibScript {
+    let mut script = "#!/usr/bin/env python\n# -*- coding: utf-8 -*-\n".to_owned();
+    script += PRELUDE;
+    MatplotlibScript { script }
+  }
+
+  /// append a string of Python script.
+  pub fn exec<S: AsRef<str>>(&mut self, script: S) -> Result<&mut Self> {
+    self.script += script.as_ref();
+    self.script.push('\n');
+    Ok(self)
+  }
+
+  // save current figure as a pickle-format file.
+  pub fn dump_pickle<S: AsRef<str>>(&mut self, filename: S) -> Result<&mut Self> {
+    self.exec(pycode::dump_pickle(filename.as_ref()))
+  }
+
+  /// get the recorded script.
+  pub fn script(&self) -> &str {
+    &self.script
+  }
+
+  /// write the recorded script into a file.
+  pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(self.script.as_bytes())?;
+    Ok(())
+  }
+}
+
+impl Backend for MatplotlibScript {
+  fn figure(&mut self,
+            figsize: &Option<(f64, f64)>,
+            dpi: &Option<f64>,
+            facecolor: &Option<String>,
+            edgecolor: &Option<String>)
+            -> Result<&mut Self> {
+    self.exec(pycode::figure(figsize, dpi, facecolor, edgecolor))
+  }
+
+  fn subplot(&mut self, rows: u32, cols: u32, n: u32) -> Result<&mut Self> {
+    self.exec(pycode::subplot(rows, cols, n))
+  }
+
+  fn suptitle(&mut self, suptitle: &str) -> Result<&mut Self> {
+    self.exec(pycode::suptitle(suptitle))
+  }
+
+  fn title(&mut self,
+           title: &str,
+           loc: &Option<String>,
+           fontsize: &Option<f64>)
+           -> Result<&mut Self> {
+    self.exec(pycode::title(title, loc, fontsize))
+  }
+
+  fn xlabel(&mut self, xlabel: &str) -> Result<&mut Self> {
+    self.exec(pycode::xlabel(xlabel))
+  }
+
+  fn ylabel(&mut self, ylabel: &str) -> Result<&mut Self> {
+    self.exec(pycode::ylabel(ylabel))
+  }
+
+  fn grid(&mut self, grid: bool) -> Result<&mut Self> {
+    self.exec(pycode::grid(grid))
+  }
+
+  fn legend(&mut self, loc: &str) -> Result<&mut Self> {
+    self.exec(pycode::legend(loc))
+  }
+
+  fn xlim(&mut self, xlim: &(f64, f64)) -> Result<&mut Self> {
+    self.exec(pycode::xlim(xlim))
+  }
+
+  fn ylim(&mut self, ylim: &(f64, f64)) -> Result<&mut Self> {
+    self.exec(pycode::ylim(ylim))
+  }
+
+  fn xscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.exec(pycode::xscale(scale))
+  }
+
+  fn yscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.exec(pycode::yscale(scale))
+  }
+
+  fn colorbar(&mut self,
+              label: &Option<String>,
+              orientation: &Option<String>,
+              ticks: &Option<&[f64]>,
+              extend: &Option<String>)
+              -> Result<&mut Self> {
+    self.exec(pycode::colorbar(label, orientation, ticks, extend))
+  }
+
+  fn set_style(&mut self, stylename: &str) -> Result<&mut Self> {
+    self.exec(pycode::set_style(stylename))
+  }
+
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.exec(pycode::savefig(filename))
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    self.exec(pycode::savefig_with(filename, options))
+  }
+
+  fn savefig_bytes(&mut self, _options: &SaveOptions) -> Result<Vec<u8>> {
+    Err(Error::Unsupported("MatplotlibScript does not render figures".to_owned()))
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    self.exec(pycode::show())
+  }
+
+  fn scatter(&mut self,
+             xdata: &[f64],
+             ydata: &[f64],
+             label: &Option<String>,
+             color: &Option<String>,
+             marker: &Option<String>)
+             -> Result<&mut Self> {
+    self.exec(pycode::scatter(Arrays::Literal, xdata, ydata, label, color, marker))
+  }
+
+  fn plot(&mut self,
+          xdata: &[f64],
+          ydata: &[f64],
+          label: &Option<String>,
+          color: &Option<String>,
+          marker: &Option<String>,
+          linestyle: &Option<String>,
+          linewidth: &Option<f64>)
+          -> Result<&mut Self> {
+    self.exec(pycode::plot(Arrays::Literal, xdata, ydata, label, color, marker, linestyle, linewidth))
+  }
+
+  fn fill_between(&mut self,
+                  x: &[f64],
+                  y1: &[f64],
+                  y2: &[f64],
+                  where_: &Option<&[bool]>,
+                  interpolate: bool,
+                  step: &Option<String>,
+                  label: &Option<String>)
+                  -> Result<&mut Self> {
+    self.exec(pycode::fill_between(Arrays::Literal, x, y1, y2, where_, interpolate, step, label))
+  }
+
+  fn bar(&mut self,
+         x: &[f64],
+         height: &[f64],
+         width: &Option<f64>,
+         bottom: &Option<&[f64]>,
+         horizontal: bool,
+         label: &Option<String>,
+         color: &Option<String>,
+         edgecolor: &Option<String>,
+         align: &Option<String>)
+         -> Result<&mut Self> {
+    self.exec(pycode::bar(Arrays::Literal, x, height, width, bottom, horizontal, label, color, edgecolor, align))
+  }
+
+  fn hist(&mut self,
+          data: &[&[f64]],
+          bins: &Option<Bins>,
+          weights: &Option<&[&[f64]]>,
+          density: bool,
+          cumulative: bool,
+          histtype: &Option<String>,
+          label: &Option<String>,
+          color: &Option<String>)
+          -> Result<&mut Self> {
+    self.exec(pycode::hist(Arrays::Literal, data, bins, weights, density, cumulative, histtype, label, color))
+  }
+
+  fn errorbar(&mut self,
+              xdata: &[f64],
+              ydata: &[f64],
+              xerr: &Option<Deviation>,
+              yerr: &Option<Deviation>,
+              capsize: &Option<f64>,
+              ecolor: &Option<String>,
+              label: &Option<String>,
+              color: &Option<String>,
+              marker: &Option<String>,
+              linestyle: &Option<String>,
+              linewidth: &Option<f64>)
+              -> Result<&mut Self> {
+    self.exec(pycode::errorbar(Arrays::Literal, xdata, ydata, xerr, yerr, capsize, ecolor, label, color, marker, linestyle, linewidth))
+  }
+
+  fn imshow(&mut self,
+            data: &[f64],
+            shape: (usize, usize),
+            cmap: &Option<String>,
+            vmin: &Option<f64>,
+            vmax: &Option<f64>,
+            interpolation: &Option<String>,
+            origin: &Option<String>,
+            extent: &Option<(f64, f64, f64, f64)>)
+            -> Result<&mut Self> {
+    self.exec(pycode::imshow(Arrays::Literal, data, shape, cmap, vmin, vmax, interpolation, origin, extent))
+  }
+
+  fn contour(&mut self,
+             x: &[f64],
+             y: &[f64],
+             z: &[f64],
+             levels: &Option<Levels>,
+             cmap: &Option<String>,
+             linewidths: &Option<f64>,
+             clabel: bool)
+             -> Result<&mut Self> {
+    self.exec(pycode::contour(Arrays::Literal, x, y, z, levels, cmap, linewidths, clabel))
+  }
+
+  fn contourf(&mut self,
+              x: &[f64],
+              y: &[f64],
+              z: &[f64],
+              levels: &Option<Levels>,
+              cmap: &Option<String>)
+              -> Result<&mut Self> {
+    self.exec(pycode::contourf(Arrays::Literal, x, y, z, levels, cmap))
+  }
+
+  fn tight_layout(&mut self) -> Result<&mut Self> {
+    self.exec(pycode::tight_layout())
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::MatplotlibScript;
+  use {Axes2D, Backend, Line2D};
+
+  #[test]
+  fn records_operations() {
+    let x = [0.0, 1.0];
+    let mut mpl = MatplotlibScript::new();
+    Axes2D::default()
+      .add(Line2D::new("Bob's data").data(&x, &x))
+      .xlabel("time")
+      .apply(&mut mpl)
+      .unwrap();
+    mpl.savefig("out.png").unwrap();
+
+    let script = mpl.script();
+    assert!(script.starts_with("#!/usr/bin/env python\n"));
+    assert!(script.contains("import matplotlib.pyplot as plt\n"));
+    assert!(script.contains("plt.plot([0.0,1.0], [0.0,1.0], label=u'Bob\\'s data', "));
+    assert!(script.contains("plt.xlabel(u'time')\n"));
+    assert!(script.ends_with("plt.savefig(u'out.png')\n"));
+  }
+
+  #[test]
+  fn savefig_bytes_unsupported() {
+    assert!(MatplotlibScript::new().savefig_bytes(&Default::default()).is_err());
+  }
+}
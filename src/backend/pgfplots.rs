@@ -0,0 +1,519 @@
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use axes2d::Scale;
+use backend::Backend;
+use backend::scene::{Align, Axes, Scene, Series, area_runs, dash_pattern, fixed, legend_position,
+                     number, parse_color};
+use error::{Error, Result};
+use savefig::SaveOptions;
+
+/// Exports figures as PGFPlots code, to be included into LaTeX documents.
+///
+/// Each axes becomes an `axis` environment in a `tikzpicture`, so the texts are typeset with the
+/// fonts of the document. Same as matplotlib, the special characters in titles and labels are
+/// escaped, except in math segments between `$` (e.g. `"$\\alpha$"`).
+/// The document requires `\usepackage{pgfplots}` and `\usepgfplotslibrary{fillbetween}`.
+/// Only `Line2D`, `Scatter` and `FillBetween` with linear or log scales are supported.
+#[derive(Debug, Clone, Default)]
+pub struct Pgfplots {
+  scene: Scene,
+  standalone: bool,
+  data_files: bool,
+}
+
+impl Pgfplots {
+  pub fn new() -> Pgfplots {
+    Pgfplots::default()
+  }
+
+  /// set whether the output is a complete document (with the class `standalone`), instead of
+  /// a `tikzpicture` to be `\input`.
+  pub fn standalone(mut self, enabled: bool) -> Self {
+    self.standalone = enabled;
+    self
+  }
+
+  /// set whether `savefig()` writes the data into the companion files, instead of inline tables.
+  ///
+  /// the files are named `<filename without extension>-<k>.dat`, and referred from the code by
+  /// the same paths, so LaTeX must be run in the working directory of the program.
+  pub fn data_files(mut self, enabled: bool) -> Self {
+    self.data_files = enabled;
+    self
+  }
+
+  /// generate the code of the current figure, with the data written inline.
+  pub fn render(&self) -> Result<String> {
+    self.document(None).map(|(code, _)| code)
+  }
+
+  /// generate the code, and the data files when `prefix` is given.
+  fn document(&self, prefix: Option<&str>) -> Result<(String, Vec<(String, String)>)> {
+    let mut writer = Writer {
+      code: String::new(),
+      prefix,
+      files: Vec::new(),
+      paths: 0,
+    };
+    if self.standalone {
+      writer.code += "\\documentclass{standalone}\n\
+                      \\usepackage{pgfplots}\n\
+                      \\usepgfplotslibrary{fillbetween}\n\
+                      \\pgfplotsset{compat=1.16}\n\
+                      \\begin{document}\n";
+    } else {
+      writer.code += "% requires \\usepackage{pgfplots} and \\usepgfplotslibrary{fillbetween}.\n";
+    }
+    writer.code += "\\begin{tikzpicture}\n";
+
+    let (width, height) = self.scene.figsize();
+    if let Some(ref suptitle) = self.scene.suptitle {
+      let _ = writeln!(writer.code,
+                       "\\node[anchor=south, font=\\large] at ({}in,0in) {{{}}};",
+                       fixed(width / 2.0),
+                       latex(suptitle));
+    }
+    for (i, axes) in self.scene.axes.iter().enumerate() {
+      let cell_width = width / axes.cols as f64;
+      let cell_height = height / axes.rows as f64;
+      let (row, col) = axes.cell();
+      let origin = (col as f64 * cell_width, -(row as f64) * cell_height);
+      writer.axes(i, axes, origin, (cell_width, cell_height))?;
+    }
+
+    writer.code += "\\end{tikzpicture}\n";
+    if self.standalone {
+      writer.code += "\\end{document}\n";
+    }
+    Ok((writer.code, writer.files))
+  }
+}
+
+impl Backend for Pgfplots {
+  delegate_to_scene!();
+
+  /// write the current figure into a `.tex` file (and the data files, if enabled).
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.savefig_with(filename, &SaveOptions::default())
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    if let Some(format) = options.format {
+      return Err(Error::Unsupported(format!("cannot save the figure as {}", format.as_str())));
+    }
+    let path = Path::new(filename);
+    match path.extension().and_then(|e| e.to_str()) {
+      None => (),
+      Some(ext) if ext.eq_ignore_ascii_case("tex") => (),
+      Some(ext) => return Err(Error::Unsupported(format!("cannot save the figure as {}", ext))),
+    }
+    let prefix = path.with_extension("").to_string_lossy().into_owned();
+    let prefix = if self.data_files { Some(prefix.as_str()) } else { None };
+    let (code, files) = self.document(prefix)?;
+    for (name, table) in files {
+      File::create(name)?.write_all(table.as_bytes())?;
+    }
+    File::create(filename)?.write_all(code.as_bytes())?;
+    Ok(self)
+  }
+
+  /// generate the code of the current figure, with the data written inline.
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    if let Some(format) = options.format {
+      return Err(Error::Unsupported(format!("cannot render the figure as {}", format.as_str())));
+    }
+    self.render().map(String::into_bytes)
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    Err(Error::Unsupported("Pgfplots cannot show the figure".to_owned()))
+  }
+}
+
+
+struct Writer<'a> {
+  code: String,
+  /// the prefix of data files, or `None` to write the tables inline.
+  prefix: Option<&'a str>,
+  files: Vec<(String, String)>,
+  /// the number of named paths, used by `fill between`.
+  paths: usize,
+}
+
+impl<'a> Writer<'a> {
+  fn axes(&mut self,
+          index: usize,
+          axes: &Axes,
+          (x, y): (f64, f64),
+          (width, height): (f64, f64))
+          -> Result<()> {
+    let mut options = vec![format!("name=axes{}", index + 1),
+                           format!("at={{({}in,{}in)}}", fixed(x), fixed(y)),
+                           "anchor=outer north west".to_owned(),
+                           format!("width={}in", fixed(width)),
+                           format!("height={}in", fixed(height)),
+                           "unbounded coords=jump".to_owned()];
+    if let Some(ref title) = axes.title {
+      options.push(format!("title={{{}}}", latex(title)));
+      match axes.title_loc.as_deref() {
+        Some("left") => options.push("title style={at={(0,1)}, anchor=south west}".to_owned()),
+        Some("right") => options.push("title style={at={(1,1)}, anchor=south east}".to_owned()),
+        _ => (),
+      }
+    }
+    if let Some(ref xlabel) = axes.xlabel {
+      options.push(format!("xlabel={{{}}}", latex(xlabel)));
+    }
+    if let Some(ref ylabel) = axes.ylabel {
+      options.push(format!("ylabel={{{}}}", latex(ylabel)));
+    }
+    options.extend(limits("x", axes.xscale, axes.xlim)?);
+    options.extend(limits("y", axes.yscale, axes.ylim)?);
+    if axes.grid {
+      options.push("grid=major".to_owned());
+    }
+    if let Some(ref loc) = axes.legend {
+      options.push(legend_style(loc));
+      options.push("legend cell align=left".to_owned());
+    }
+
+    let _ = writeln!(self.code, "\\begin{{axis}}[\n  {}]", options.join(",\n  "));
+    for series in &axes.series {
+      self.series(series, axes.legend.is_some())?;
+    }
+    self.code += "\\end{axis}\n";
+    Ok(())
+  }
+
+  fn series(&mut self, series: &Series, legend: bool) -> Result<()> {
+    let color = color(series.color());
+    let label = series.label().filter(|_| legend);
+    match *series {
+      Series::Line { ref x, ref y, ref marker, ref linestyle, linewidth, .. } => {
+        let linewidth = linewidth.unwrap_or(1.5);
+        let mut options = vec![format!("color={}", color)];
+        match dash_pattern(linestyle, linewidth)? {
+          Some(dash) => {
+            options.push(format!("line width={}pt", fixed(linewidth)));
+            if !dash.is_empty() {
+              let pattern: Vec<_> = dash.chunks(2)
+                .map(|c| format!("on {}pt off {}pt", fixed(c[0]), fixed(c[1])))
+                .collect();
+              options.push(format!("dash pattern={}", pattern.join(" ")));
+            }
+          }
+          None if marker.is_some() => options.push("only marks".to_owned()),
+          // neither the line nor markers are drawn.
+          None => return Ok(()),
+        }
+        options.extend(marker.as_deref().map(mark).unwrap_or_else(|| vec!["mark=none".to_owned()]));
+        self.plot(options, label, &["x", "y"], &[x, y]);
+      }
+      Series::Scatter { ref x, ref y, ref marker, .. } => {
+        let mut options = vec![format!("color={}", color), "only marks".to_owned()];
+        options.extend(mark(marker.as_deref().unwrap_or("o")));
+        self.plot(options, label, &["x", "y"], &[x, y]);
+      }
+      Series::Area { ref x, ref y1, ref y2, ref where_, .. } => {
+        for (i, run) in area_runs(x, y1, y2, where_).iter().enumerate() {
+          let columns: Vec<Vec<f64>> = vec![run.iter().map(|p| p.0).collect(),
+                                            run.iter().map(|p| p.1).collect(),
+                                            run.iter().map(|p| p.2).collect()];
+          let (keys, source) = self.table(&["x", "y1", "y2"],
+                                          &[&columns[0], &columns[1], &columns[2]]);
+          let (lower, upper) = (self.paths + 1, self.paths + 2);
+          self.paths += 2;
+          for &(path, column) in &[(lower, "y1"), (upper, "y2")] {
+            let mut keys = keys.clone();
+            keys.insert(0, format!("y={}", column));
+            keys.insert(0, "x=x".to_owned());
+            let _ = writeln!(self.code,
+                             "\\addplot[name path=path{}, draw=none, forget plot] table[{}] {};",
+                             path,
+                             keys.join(", "),
+                             source);
+          }
+          // the legend shows the first run only.
+          let label = label.filter(|_| i == 0);
+          let _ = writeln!(self.code,
+                           "\\addplot[fill={}, draw=none, {}] fill between[of=path{} and path{}];",
+                           color,
+                           if label.is_some() { "area legend" } else { "forget plot" },
+                           lower,
+                           upper);
+          if let Some(label) = label {
+            let _ = writeln!(self.code, "\\addlegendentry{{{}}}", latex(label));
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// add a plot of the table, with the legend entry if `label` is given.
+  fn plot(&mut self,
+          mut options: Vec<String>,
+          label: Option<&str>,
+          names: &[&str],
+          columns: &[&[f64]]) {
+    if label.is_none() {
+      options.push("forget plot".to_owned());
+    }
+    let (keys, source) = self.table(names, columns);
+    let table = if keys.is_empty() {
+      "table".to_owned()
+    } else {
+      format!("table[{}]", keys.join(", "))
+    };
+    let _ = writeln!(self.code, "\\addplot[{}] {} {};", options.join(", "), table, source);
+    if let Some(label) = label {
+      let _ = writeln!(self.code, "\\addlegendentry{{{}}}", latex(label));
+    }
+  }
+
+  /// the options and the source of a table, which is written inline or into a data file.
+  fn table(&mut self, names: &[&str], columns: &[&[f64]]) -> (Vec<String>, String) {
+    let rows = columns.iter().map(|c| c.len()).min().unwrap_or(0);
+    let row = |i: usize| columns.iter().map(|c| number(c[i])).collect::<Vec<_>>().join(" ");
+    match self.prefix {
+      Some(prefix) => {
+        let name = format!("{}-{}.dat", prefix, self.files.len() + 1);
+        let mut data = names.join(" ") + "\n";
+        for i in 0..rows {
+          data += &row(i);
+          data.push('\n');
+        }
+        let source = format!("{{{}}}", name);
+        self.files.push((name, data));
+        (Vec::new(), source)
+      }
+      None => {
+        // the rows are separated by `\\`, so the code also works inside macro arguments.
+        let mut data = format!("{{\n{} \\\\\n", names.join(" "));
+        for i in 0..rows {
+          data += &row(i);
+          data += " \\\\\n";
+        }
+        data.push('}');
+        (vec!["row sep=\\\\".to_owned()], data)
+      }
+    }
+  }
+}
+
+/// the options of axis limits and the scale.
+fn limits(axis: &str, scale: Scale, limits: Option<(f64, f64)>) -> Result<Vec<String>> {
+  let mut options = Vec::new();
+  match scale {
+    Scale::Linear => (),
+    Scale::Log { base } => {
+      options.push(format!("{}mode=log", axis));
+      options.push(format!("log basis {}={}", axis, number(base)));
+    }
+    _ => return Err(Error::Unsupported("Pgfplots only supports linear and log scales".to_owned())),
+  }
+  match limits {
+    Some((lb, ub)) => {
+      options.push(format!("{}min={}", axis, number(lb)));
+      options.push(format!("{}max={}", axis, number(ub)));
+    }
+    // same as matplotlib, the data has margins.
+    None => options.push(format!("enlarge {} limits=0.05", axis)),
+  }
+  Ok(options)
+}
+
+/// the position of legend, in the axis description coordinates.
+fn legend_style(loc: &str) -> String {
+  let (horizontal, vertical) = legend_position(loc);
+  let (x, west_east) = match horizontal {
+    Align::Start => ("0.03", Some("west")),
+    Align::Center => ("0.5", None),
+    Align::End => ("0.97", Some("east")),
+  };
+  let (y, south_north) = match vertical {
+    Align::Start => ("0.03", Some("south")),
+    Align::Center => ("0.5", None),
+    Align::End => ("0.97", Some("north")),
+  };
+  let anchor = match (south_north, west_east) {
+    (Some(v), Some(h)) => format!("{} {}", v, h),
+    (Some(d), None) | (None, Some(d)) => d.to_owned(),
+    (None, None) => "center".to_owned(),
+  };
+  format!("legend style={{at={{({},{})}}, anchor={}}}", x, y, anchor)
+}
+
+/// the options of markers (in matplotlib's notation).
+fn mark(marker: &str) -> Vec<String> {
+  let (mark, size, rotate) = match marker {
+    "None" | "none" | "" | " " => return vec!["mark=none".to_owned()],
+    "." => ("*", 1.5, None),
+    "," => ("*", 0.5, None),
+    "s" => ("square*", 3.0, None),
+    "D" | "d" => ("diamond*", 3.0, None),
+    "^" => ("triangle*", 3.0, None),
+    "v" => ("triangle*", 3.0, Some(180)),
+    "<" => ("triangle*", 3.0, Some(90)),
+    ">" => ("triangle*", 3.0, Some(270)),
+    "x" => ("x", 3.0, None),
+    "+" => ("+", 3.0, None),
+    "*" => ("star", 3.0, None),
+    _ => ("*", 3.0, None),
+  };
+  let mut options = vec![format!("mark={}", mark), format!("mark size={}pt", fixed(size))];
+  match rotate {
+    Some(angle) => options.push(format!("mark options={{solid, rotate={}}}", angle)),
+    None => options.push("mark options={solid}".to_owned()),
+  }
+  options
+}
+
+/// a color in xcolor's notation. the names unknown to matplotlib are passed to xcolor.
+fn color(color: &str) -> String {
+  match parse_color(color) {
+    Some((r, g, b)) => format!("{{rgb,255:red,{};green,{};blue,{}}}", r, g, b),
+    None => color.to_owned(),
+  }
+}
+
+/// escape the special characters of LaTeX in a text, except the math segments between `$`.
+///
+/// `\$` is a literal dollar sign, and all `$` are literal if they are not paired.
+fn latex(text: &str) -> String {
+  let dollars = text.matches('$').count() - text.matches("\\$").count();
+  let math_enabled = dollars.is_multiple_of(2);
+  let mut escaped = String::with_capacity(text.len());
+  let mut math = false;
+  let mut chars = text.chars().peekable();
+  while let Some(c) = chars.next() {
+    if math {
+      if c == '$' {
+        math = false;
+      }
+      escaped.push(c);
+      continue;
+    }
+    match c {
+      '$' if math_enabled => {
+        math = true;
+        escaped.push(c);
+      }
+      '\\' if chars.peek() == Some(&'$') => {
+        chars.next();
+        escaped += "\\$";
+      }
+      '\\' => escaped += "\\textbackslash{}",
+      '~' => escaped += "\\textasciitilde{}",
+      '^' => escaped += "\\^{}",
+      '$' | '{' | '}' | '%' | '&' | '#' | '_' => {
+        escaped.push('\\');
+        escaped.push(c);
+      }
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+
+#[cfg(test)]
+mod tests {
+  use std::env;
+  use std::fs;
+  use super::{Pgfplots, latex, legend_style};
+  use axes2d::{Axes2D, FillBetween, Line2D, Scale, Scatter};
+  use backend::Backend;
+  use figure::{Figure, Subplots};
+  use savefig::{Format, SaveOptions};
+
+  #[test]
+  fn render_axes() {
+    let x = [0.0, 1.0, 2.0];
+    let y = [1.0, f64::NAN, -3.0];
+    let mut tex = Pgfplots::new();
+    Figure::default()
+      .subplots(Subplots::new(2, 1)
+                  .at(0,
+                      Axes2D::default()
+                        .add(Line2D::new("$y$").data(&x, &y).linestyle("--").color("k"))
+                        .add(Scatter::new("").data(&x, &x).marker("s"))
+                        .xlabel("time_s (50%)")
+                        .xlim(0.0, 2.0)
+                        .grid(true)
+                        .legend("upper left"))
+                  .at(1,
+                      Axes2D::default()
+                        .add(FillBetween::default().data(&x, &x, &y).label("band"))
+                        .yscale(Scale::Log { base: 2.0 })
+                        .legend("best")))
+      .apply(&mut tex)
+      .unwrap();
+    let code = tex.render().unwrap();
+
+    assert!(code.starts_with("% requires \\usepackage{pgfplots}"));
+    assert!(code.contains("\\begin{axis}[\n  name=axes1,\n  at={(0in,0in)},\n  anchor=outer north west,\n  \
+                           width=6.4in,\n  height=2.4in,\n"));
+    assert!(code.contains("  xlabel={time\\_s (50\\%)},\n  xmin=0,\n  xmax=2,\n  enlarge y limits=0.05,\n  grid=major,\n  \
+                           legend style={at={(0.03,0.97)}, anchor=north west},\n"));
+    assert!(code.contains("\\addplot[color={rgb,255:red,0;green,0;blue,0}, line width=1.5pt, \
+                           dash pattern=on 5.55pt off 2.4pt, mark=none] table[row sep=\\\\] {\n\
+                           x y \\\\\n0 1 \\\\\n1 nan \\\\\n2 -3 \\\\\n};\n\\addlegendentry{$y$}\n"));
+    // the unlabeled series is not in the legend.
+    assert!(code.contains("only marks, mark=square*, mark size=3pt, mark options={solid}, forget plot]"));
+    assert!(code.contains("at={(0in,-2.4in)}"));
+    assert!(code.contains("  ymode=log,\n  log basis y=2,\n"));
+    assert!(code.contains("\\addplot[name path=path1, draw=none, forget plot] table[x=x, y=y1, row sep=\\\\] {\n\
+                           x y1 y2 \\\\\n0 0 1 \\\\\n};\n"));
+    // the area is split by NaN, but shown in the legend once.
+    assert!(code.contains("draw=none, area legend] fill between[of=path1 and path2];\n\
+                           \\addlegendentry{band}\n"));
+    assert!(code.contains("draw=none, forget plot] fill between[of=path3 and path4];\n"));
+    assert!(code.ends_with("\\end{axis}\n\\end{tikzpicture}\n"));
+  }
+
+  #[test]
+  fn data_files() {
+    let x = [0.0, 1.0];
+    let dir = env::temp_dir().join(format!("rustplotlib-pgfplots-{}", ::std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("figure.tex");
+    let filename = filename.to_str().unwrap();
+
+    let mut tex = Pgfplots::new().standalone(true).data_files(true);
+    Axes2D::default().add(Line2D::new("data").data(&x, &x)).apply(&mut tex).unwrap();
+    tex.savefig(filename).unwrap();
+
+    let code = fs::read_to_string(filename).unwrap();
+    let table = dir.join("figure-1.dat");
+    assert!(code.starts_with("\\documentclass{standalone}\n"));
+    assert!(code.contains(&format!("table {{{}}};", table.to_str().unwrap())));
+    assert!(code.ends_with("\\end{document}\n"));
+    assert_eq!(fs::read_to_string(&table).unwrap(), "x y\n0 0\n1 1\n");
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn unsupported() {
+    let mut tex = Pgfplots::new();
+    tex.xscale(&Scale::SymLog { linthresh: 1.0 }).unwrap();
+    assert!(tex.render().is_err());
+
+    let mut tex = Pgfplots::new();
+    assert!(tex.savefig_bytes(&SaveOptions::default().format(Format::Pdf)).is_err());
+    assert!(tex.savefig("figure.pdf").is_err());
+  }
+
+  #[test]
+  fn formatting() {
+    assert_eq!(legend_style("center"), "legend style={at={(0.5,0.5)}, anchor=center}");
+    assert_eq!(legend_style("lower center"), "legend style={at={(0.5,0.03)}, anchor=south}");
+    assert_eq!(latex("50% R&D #runs time_s ~x^2 {a} \\"),
+               "50\\% R\\&D \\#runs time\\_s \\textasciitilde{}x\\^{}2 \\{a\\} \\textbackslash{}");
+    assert_eq!(latex("$\\alpha_1$ in 100%"), "$\\alpha_1$ in 100\\%");
+    assert_eq!(latex("cost \\$5"), "cost \\$5");
+    assert_eq!(latex("$5"), "\\$5");
+  }
+}
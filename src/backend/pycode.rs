@@ -0,0 +1,682 @@
+use axes2d::{Bins, Deviation, Levels, Scale};
+use savefig::{Format, SaveOptions};
+
+/// Python script executed at first, which imports modules and defines helper functions.
+///
+/// `_rustplotlib_array()` decodes an array of `f64` transferred as base64-encoded
+/// little-endian bytes, which is much faster than parsing a list of decimal literals
+/// and preserves NaN and infinity.
+pub const PRELUDE: &str = r#"
+import io
+import base64
+import numpy as np
+import matplotlib.pyplot as plt
+
+def _rustplotlib_array(data):
+    return np.frombuffer(base64.b64decode(data), dtype='<f8')
+"#;
+
+/// How the arrays of data are written in the generated code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arrays {
+  /// base64-encoded binary decoded by `_rustplotlib_array()`, which is fast to transfer.
+  Binary,
+  /// list literals, which can be read and edited by hand.
+  Literal,
+}
+
+impl Arrays {
+  /// convert an array of `f64` into a Python expression.
+  pub fn array(self, data: &[f64]) -> String {
+    match self {
+      Arrays::Binary => to_pyarray(data),
+      Arrays::Literal => to_pyvec(data),
+    }
+  }
+
+  /// convert a row-major 2D array into a 2D numpy array.
+  pub fn array2(self, data: &[f64], (rows, cols): (usize, usize)) -> String {
+    match self {
+      Arrays::Binary => format!("{}.reshape({}, {})", to_pyarray(data), rows, cols),
+      Arrays::Literal => {
+        let rows: Vec<String> = data.chunks(cols.max(1)).map(to_pyvec).collect();
+        format!("np.array([{}])", rows.join(", "))
+      }
+    }
+  }
+
+  /// convert arrays of `f64` into a list of them.
+  fn list(self, data: &[&[f64]]) -> String {
+    let data: Vec<String> = data.iter().map(|d| self.array(d)).collect();
+    format!("[{}]", data.join(", "))
+  }
+
+  fn deviation(self, err: &Deviation) -> String {
+    match *err {
+      Deviation::Symmetric(err) => self.array(err),
+      Deviation::Asymmetric(lower, upper) => format!("[{}, {}]", self.array(lower), self.array(upper)),
+    }
+  }
+}
+
+/// save current figure as a pickle-format file.
+pub fn dump_pickle(filename: &str) -> String {
+  format!("import pickle\npickle.dump(plt.gcf(), open({}, 'wb'))",
+          filename.to_pystr())
+}
+
+pub fn figure(figsize: &Option<(f64, f64)>,
+              dpi: &Option<f64>,
+              facecolor: &Option<String>,
+              edgecolor: &Option<String>)
+              -> String {
+  let mut code = "plt.figure(".to_owned();
+  if let Some((width, height)) = *figsize {
+    code += &format!("figsize=({}, {}), ", width.to_pystr(), height.to_pystr());
+  }
+  if let Some(ref dpi) = *dpi {
+    code += &format!("dpi={}, ", dpi.to_pystr());
+  }
+  if let Some(ref facecolor) = *facecolor {
+    code += &format!("facecolor={}, ", facecolor.to_pystr());
+  }
+  if let Some(ref edgecolor) = *edgecolor {
+    code += &format!("edgecolor={}, ", edgecolor.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+pub fn subplot(rows: u32, cols: u32, n: u32) -> String {
+  format!("plt.subplot({}, {}, {})", rows, cols, n)
+}
+
+pub fn suptitle(suptitle: &str) -> String {
+  format!("plt.suptitle({})", suptitle.to_pystr())
+}
+
+pub fn title(title: &str, loc: &Option<String>, fontsize: &Option<f64>) -> String {
+  let mut code = format!("plt.title({}, ", title.to_pystr());
+  if let Some(ref loc) = *loc {
+    code += &format!("loc={}, ", loc.to_pystr());
+  }
+  if let Some(ref fontsize) = *fontsize {
+    code += &format!("fontsize={}, ", fontsize.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+pub fn xlabel(xlabel: &str) -> String {
+  format!("plt.xlabel({})", xlabel.to_pystr())
+}
+
+pub fn ylabel(ylabel: &str) -> String {
+  format!("plt.ylabel({})", ylabel.to_pystr())
+}
+
+pub fn grid(grid: bool) -> String {
+  format!("plt.grid({})", grid.to_pystr())
+}
+
+pub fn legend(loc: &str) -> String {
+  format!("plt.legend(loc={})", loc.to_pystr())
+}
+
+pub fn xlim(xlim: &(f64, f64)) -> String {
+  format!("plt.xlim(({}, {}))", xlim.0.to_pystr(), xlim.1.to_pystr())
+}
+
+pub fn ylim(ylim: &(f64, f64)) -> String {
+  format!("plt.ylim(({}, {}))", ylim.0.to_pystr(), ylim.1.to_pystr())
+}
+
+pub fn xscale(scale: &Scale) -> String {
+  format!("plt.xscale({})", scale.to_pystr())
+}
+
+pub fn yscale(scale: &Scale) -> String {
+  format!("plt.yscale({})", scale.to_pystr())
+}
+
+pub fn colorbar(label: &Option<String>,
+                orientation: &Option<String>,
+                ticks: &Option<&[f64]>,
+                extend: &Option<String>)
+                -> String {
+  let mut code = "plt.colorbar(".to_owned();
+  if let Some(ref label) = *label {
+    code += &format!("label={}, ", label.to_pystr());
+  }
+  if let Some(ref orientation) = *orientation {
+    code += &format!("orientation={}, ", orientation.to_pystr());
+  }
+  if let Some(ticks) = *ticks {
+    code += &format!("ticks={}, ", to_pyvec(ticks));
+  }
+  if let Some(ref extend) = *extend {
+    code += &format!("extend={}, ", extend.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+pub fn set_style(stylename: &str) -> String {
+  format!("plt.style.use({})", stylename.to_pystr())
+}
+
+pub fn savefig(filename: &str) -> String {
+  format!("plt.savefig({})", filename.to_pystr())
+}
+
+pub fn savefig_with(filename: &str, options: &SaveOptions) -> String {
+  format!("plt.savefig({}, {})", filename.to_pystr(), options.to_pystr())
+}
+
+pub fn savefig_bytes(options: &SaveOptions) -> String {
+  let format = options.format.unwrap_or(Format::Png);
+  format!("_buf = io.BytesIO()\n\
+           plt.savefig(_buf, {})\n\
+           __rustplotlib_output__ = _buf.getvalue()",
+          options.clone().format(format).to_pystr())
+}
+
+pub fn show() -> String {
+  "plt.show()".to_owned()
+}
+
+pub fn scatter(arrays: Arrays,
+               xdata: &[f64],
+               ydata: &[f64],
+               label: &Option<String>,
+               color: &Option<String>,
+               marker: &Option<String>)
+               -> String {
+  let mut code = format!("plt.scatter({}, {}, ", arrays.array(xdata), arrays.array(ydata));
+  if let Some(ref label) = *label {
+    code += &format!("label={}, ", label.to_pystr());
+  }
+  if let Some(ref color) = *color {
+    code += &format!("color={}, ", color.to_pystr());
+  }
+  if let Some(ref marker) = *marker {
+    code += &format!("marker={}, ", marker.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn plot(arrays: Arrays,
+            xdata: &[f64],
+            ydata: &[f64],
+            label: &Option<String>,
+            color: &Option<String>,
+            marker: &Option<String>,
+            linestyle: &Option<String>,
+            linewidth: &Option<f64>)
+            -> String {
+  let mut code = format!("plt.plot({}, {}, ", arrays.array(xdata), arrays.array(ydata));
+  if let Some(ref label) = *label {
+    code += &format!("label={}, ", label.to_pystr());
+  }
+  if let Some(ref color) = *color {
+    code += &format!("color={}, ", color.to_pystr());
+  }
+  if let Some(ref marker) = *marker {
+    code += &format!("marker={}, ", marker.to_pystr());
+  }
+  if let Some(ref ls) = *linestyle {
+    code += &format!("linestyle={}, ", ls.to_pystr());
+  }
+  if let Some(ref lw) = *linewidth {
+    code += &format!("linewidth={}, ", lw.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn fill_between(arrays: Arrays,
+                    x: &[f64],
+                    y1: &[f64],
+                    y2: &[f64],
+                    where_: &Option<&[bool]>,
+                    interpolate: bool,
+                    step: &Option<String>,
+                    label: &Option<String>)
+                    -> String {
+  let mut code = format!("plt.fill_between({}, {}, {}, ",
+                         arrays.array(x),
+                         arrays.array(y1),
+                         arrays.array(y2));
+  if let Some(where_) = *where_ {
+    code += &format!("where={}, ", to_pyvec(where_));
+  }
+  code += &format!("interpolate={}, ", interpolate.to_pystr());
+  if let Some(ref step) = *step {
+    code += &format!("step={}, ", step.to_pystr());
+  }
+  if let Some(ref label) = *label {
+    code += &format!("label={}, ", label.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn bar(arrays: Arrays,
+           x: &[f64],
+           height: &[f64],
+           width: &Option<f64>,
+           bottom: &Option<&[f64]>,
+           horizontal: bool,
+           label: &Option<String>,
+           color: &Option<String>,
+           edgecolor: &Option<String>,
+           align: &Option<String>)
+           -> String {
+  // `plt.barh()` takes the thickness as `height` and the base as `left`.
+  let (func, width_key, bottom_key) = if horizontal {
+    ("barh", "height", "left")
+  } else {
+    ("bar", "width", "bottom")
+  };
+  let mut code = format!("plt.{}({}, {}, ", func, arrays.array(x), arrays.array(height));
+  if let Some(ref width) = *width {
+    code += &format!("{}={}, ", width_key, width.to_pystr());
+  }
+  if let Some(bottom) = *bottom {
+    code += &format!("{}={}, ", bottom_key, arrays.array(bottom));
+  }
+  if let Some(ref label) = *label {
+    code += &format!("label={}, ", label.to_pystr());
+  }
+  if let Some(ref color) = *color {
+    code += &format!("color={}, ", color.to_pystr());
+  }
+  if let Some(ref edgecolor) = *edgecolor {
+    code += &format!("edgecolor={}, ", edgecolor.to_pystr());
+  }
+  if let Some(ref align) = *align {
+    code += &format!("align={}, ", align.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn hist(arrays: Arrays,
+            data: &[&[f64]],
+            bins: &Option<Bins>,
+            weights: &Option<&[&[f64]]>,
+            density: bool,
+            cumulative: bool,
+            histtype: &Option<String>,
+            label: &Option<String>,
+            color: &Option<String>)
+            -> String {
+  let mut code = format!("plt.hist({}, ", arrays.list(data));
+  if let Some(ref bins) = *bins {
+    code += &format!("bins={}, ", bins.to_pystr());
+  }
+  if let Some(weights) = *weights {
+    code += &format!("weights={}, ", arrays.list(weights));
+  }
+  code += &format!("density={}, ", density.to_pystr());
+  code += &format!("cumulative={}, ", cumulative.to_pystr());
+  if let Some(ref histtype) = *histtype {
+    code += &format!("histtype={}, ", histtype.to_pystr());
+  }
+  if let Some(ref label) = *label {
+    code += &format!("label={}, ", label.to_pystr());
+  }
+  if let Some(ref color) = *color {
+    code += &format!("color={}, ", color.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn errorbar(arrays: Arrays,
+                xdata: &[f64],
+                ydata: &[f64],
+                xerr: &Option<Deviation>,
+                yerr: &Option<Deviation>,
+                capsize: &Option<f64>,
+                ecolor: &Option<String>,
+                label: &Option<String>,
+                color: &Option<String>,
+                marker: &Option<String>,
+                linestyle: &Option<String>,
+                linewidth: &Option<f64>)
+                -> String {
+  let mut code = format!("plt.errorbar({}, {}, ", arrays.array(xdata), arrays.array(ydata));
+  if let Some(ref xerr) = *xerr {
+    code += &format!("xerr={}, ", arrays.deviation(xerr));
+  }
+  if let Some(ref yerr) = *yerr {
+    code += &format!("yerr={}, ", arrays.deviation(yerr));
+  }
+  if let Some(ref capsize) = *capsize {
+    code += &format!("capsize={}, ", capsize.to_pystr());
+  }
+  if let Some(ref ecolor) = *ecolor {
+    code += &format!("ecolor={}, ", ecolor.to_pystr());
+  }
+  if let Some(ref label) = *label {
+    code += &format!("label={}, ", label.to_pystr());
+  }
+  if let Some(ref color) = *color {
+    code += &format!("color={}, ", color.to_pystr());
+  }
+  if let Some(ref marker) = *marker {
+    code += &format!("marker={}, ", marker.to_pystr());
+  }
+  if let Some(ref ls) = *linestyle {
+    code += &format!("linestyle={}, ", ls.to_pystr());
+  }
+  if let Some(ref lw) = *linewidth {
+    code += &format!("linewidth={}, ", lw.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn imshow(arrays: Arrays,
+              data: &[f64],
+              shape: (usize, usize),
+              cmap: &Option<String>,
+              vmin: &Option<f64>,
+              vmax: &Option<f64>,
+              interpolation: &Option<String>,
+              origin: &Option<String>,
+              extent: &Option<(f64, f64, f64, f64)>)
+              -> String {
+  let mut code = format!("plt.imshow({}, ", arrays.array2(data, shape));
+  if let Some(ref cmap) = *cmap {
+    code += &format!("cmap={}, ", cmap.to_pystr());
+  }
+  if let Some(ref vmin) = *vmin {
+    code += &format!("vmin={}, ", vmin.to_pystr());
+  }
+  if let Some(ref vmax) = *vmax {
+    code += &format!("vmax={}, ", vmax.to_pystr());
+  }
+  if let Some(ref interpolation) = *interpolation {
+    code += &format!("interpolation={}, ", interpolation.to_pystr());
+  }
+  if let Some(ref origin) = *origin {
+    code += &format!("origin={}, ", origin.to_pystr());
+  }
+  if let Some((left, right, bottom, top)) = *extent {
+    code += &format!("extent=({}, {}, {}, {}), ",
+                     left.to_pystr(),
+                     right.to_pystr(),
+                     bottom.to_pystr(),
+                     top.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn contour(arrays: Arrays,
+               x: &[f64],
+               y: &[f64],
+               z: &[f64],
+               levels: &Option<Levels>,
+               cmap: &Option<String>,
+               linewidths: &Option<f64>,
+               clabel: bool)
+               -> String {
+  let mut code = format!("plt.contour({}, {}, {}, ",
+                         arrays.array(x),
+                         arrays.array(y),
+                         arrays.array2(z, (y.len(), x.len())));
+  if let Some(ref levels) = *levels {
+    code += &format!("levels={}, ", levels.to_pystr());
+  }
+  if let Some(ref cmap) = *cmap {
+    code += &format!("cmap={}, ", cmap.to_pystr());
+  }
+  if let Some(ref lw) = *linewidths {
+    code += &format!("linewidths={}, ", lw.to_pystr());
+  }
+  code += ")";
+  if clabel {
+    code = format!("plt.clabel({}, inline=True)", code);
+  }
+  code
+}
+
+pub fn contourf(arrays: Arrays,
+                x: &[f64],
+                y: &[f64],
+                z: &[f64],
+                levels: &Option<Levels>,
+                cmap: &Option<String>)
+                -> String {
+  let mut code = format!("plt.contourf({}, {}, {}, ",
+                         arrays.array(x),
+                         arrays.array(y),
+                         arrays.array2(z, (y.len(), x.len())));
+  if let Some(ref levels) = *levels {
+    code += &format!("levels={}, ", levels.to_pystr());
+  }
+  if let Some(ref cmap) = *cmap {
+    code += &format!("cmap={}, ", cmap.to_pystr());
+  }
+  code += ")";
+  code
+}
+
+pub fn tight_layout() -> String {
+  "plt.tight_layout()".to_owned()
+}
+
+pub trait ToPyStr {
+  fn to_pystr(&self) -> String;
+}
+impl ToPyStr for str {
+  /// encode as a Python unicode literal.
+  ///
+  /// non-ASCII characters are escaped so that the literal has the same meaning in
+  /// both Python 2 and 3.
+  fn to_pystr(&self) -> String {
+    let mut literal = "u'".to_owned();
+    for c in self.chars() {
+      match c {
+        '\\' => literal += "\\\\",
+        '\'' => literal += "\\'",
+        '\n' => literal += "\\n",
+        '\r' => literal += "\\r",
+        '\t' => literal += "\\t",
+        ' '..='~' => literal.push(c),
+        c if (c as u32) < 0x100 => literal += &format!("\\x{:02x}", c as u32),
+        c if (c as u32) < 0x10000 => literal += &format!("\\u{:04x}", c as u32),
+        c => literal += &format!("\\U{:08x}", c as u32),
+      }
+    }
+    literal.push('\'');
+    literal
+  }
+}
+impl ToPyStr for f64 {
+  fn to_pystr(&self) -> String {
+    if self.is_nan() {
+      "float('nan')".to_owned()
+    } else if self.is_infinite() {
+      format!("{}float('inf')", if *self < 0.0 { "-" } else { "" })
+    } else {
+      format!("{:?}", self)
+    }
+  }
+}
+impl ToPyStr for bool {
+  fn to_pystr(&self) -> String {
+    if *self {
+      "True".to_owned()
+    } else {
+      "False".to_owned()
+    }
+  }
+}
+
+impl<'a> ToPyStr for Bins<'a> {
+  fn to_pystr(&self) -> String {
+    match *self {
+      Bins::Count(n) => format!("{}", n),
+      Bins::Edges(edges) => to_pyvec(edges),
+    }
+  }
+}
+impl<'a> ToPyStr for Levels<'a> {
+  fn to_pystr(&self) -> String {
+    match *self {
+      Levels::Count(n) => format!("{}", n),
+      Levels::Values(values) => to_pyvec(values),
+    }
+  }
+}
+impl ToPyStr for Scale {
+  fn to_pystr(&self) -> String {
+    match *self {
+      Scale::Linear => "'linear'".to_owned(),
+      Scale::Log { base } => format!("'log', base={}", base.to_pystr()),
+      Scale::SymLog { linthresh } => format!("'symlog', linthresh={}", linthresh.to_pystr()),
+      Scale::Logit => "'logit'".to_owned(),
+    }
+  }
+}
+impl ToPyStr for SaveOptions {
+  fn to_pystr(&self) -> String {
+    let mut code = String::new();
+    if let Some(ref dpi) = self.dpi {
+      code += &format!("dpi={}, ", dpi.to_pystr());
+    }
+    if let Some(format) = self.format {
+      code += &format!("format='{}', ", format.as_str());
+    }
+    code += &format!("transparent={}, ", self.transparent.to_pystr());
+    if self.tight {
+      code += "bbox_inches='tight', ";
+    }
+    if let Some(ref pad) = self.pad_inches {
+      code += &format!("pad_inches={}, ", pad.to_pystr());
+    }
+    if !self.metadata.is_empty() {
+      let items: Vec<String> = self.metadata
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key.to_pystr(), value.to_pystr()))
+        .collect();
+      code += &format!("metadata={{{}}}, ", items.join(", "));
+    }
+    code
+  }
+}
+
+pub fn to_pyvec<T: ToPyStr>(data: &[T]) -> String {
+  let data: Vec<String> = data.iter().map(|x| x.to_pystr()).collect();
+  format!("[{}]", data.join(","))
+}
+
+/// convert an array of `f64` into a numpy array, transferred in binary form.
+fn to_pyarray(data: &[f64]) -> String {
+  let mut bytes = Vec::with_capacity(data.len() * 8);
+  for x in data {
+    bytes.extend_from_slice(&x.to_bits().to_le_bytes());
+  }
+  format!("_rustplotlib_array('{}')", base64_encode(&bytes))
+}
+
+fn base64_encode(data: &[u8]) -> String {
+  const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+  for chunk in data.chunks(3) {
+    let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+    let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+    for i in 0..4 {
+      if i <= chunk.len() {
+        encoded.push(TABLE[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+      } else {
+        encoded.push('=');
+      }
+    }
+  }
+  encoded
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::{Arrays, ToPyStr, base64_encode};
+
+  #[test]
+  fn base64() {
+    assert_eq!(base64_encode(b""), "");
+    assert_eq!(base64_encode(b"f"), "Zg==");
+    assert_eq!(base64_encode(b"fo"), "Zm8=");
+    assert_eq!(base64_encode(b"foo"), "Zm9v");
+    assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    assert_eq!(base64_encode(&[0xfb, 0xff, 0xfe]), "+//+");
+  }
+
+  #[test]
+  fn pyarray_little_endian() {
+    assert_eq!(Arrays::Binary.array(&[1.0]), "_rustplotlib_array('AAAAAAAA8D8=')");
+    assert_eq!(Arrays::Binary.array2(&[], (0, 3)), "_rustplotlib_array('').reshape(0, 3)");
+  }
+
+  #[test]
+  fn pyarray_literal() {
+    assert_eq!(Arrays::Literal.array(&[1.0, -0.5, f64::NAN]), "[1.0,-0.5,float('nan')]");
+    assert_eq!(Arrays::Literal.array2(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], (2, 3)),
+               "np.array([[1.0,2.0,3.0], [4.0,5.0,6.0]])");
+  }
+
+  #[test]
+  fn pystr_float() {
+    assert_eq!(1.0.to_pystr(), "1.0");
+    assert_eq!((-0.25).to_pystr(), "-0.25");
+    assert_eq!(1e300.to_pystr(), "1e300");
+    assert_eq!(f64::NAN.to_pystr(), "float('nan')");
+    assert_eq!(f64::INFINITY.to_pystr(), "float('inf')");
+    assert_eq!(f64::NEG_INFINITY.to_pystr(), "-float('inf')");
+  }
+
+  #[test]
+  fn pystr_plain() {
+    assert_eq!("sin(x)".to_pystr(), "u'sin(x)'");
+    assert_eq!("".to_pystr(), "u''");
+  }
+
+  #[test]
+  fn pystr_quotes() {
+    assert_eq!("Bob's data".to_pystr(), r"u'Bob\'s data'");
+    assert_eq!(r#"say "hi""#.to_pystr(), r#"u'say "hi"'"#);
+    assert_eq!("'); import os; ('".to_pystr(), r"u'\'); import os; (\''");
+  }
+
+  #[test]
+  fn pystr_backslashes() {
+    assert_eq!(r"$y_1 = \sin(x)$".to_pystr(), r"u'$y_1 = \\sin(x)$'");
+    assert_eq!(r"C:\path\".to_pystr(), r"u'C:\\path\\'");
+  }
+
+  #[test]
+  fn pystr_control_characters() {
+    assert_eq!("line 1\nline 2".to_pystr(), r"u'line 1\nline 2'");
+    assert_eq!("a\tb\r".to_pystr(), r"u'a\tb\r'");
+    assert_eq!("nul\0".to_pystr(), r"u'nul\x00'");
+  }
+
+  #[test]
+  fn pystr_non_ascii() {
+    assert_eq!("µm".to_pystr(), r"u'\xb5m'");
+    assert_eq!("温度 [℃]".to_pystr(), r"u'\u6e29\u5ea6 [\u2103]'");
+    assert_eq!("🚀".to_pystr(), r"u'\U0001f680'");
+  }
+}
@@ -0,0 +1,544 @@
+use axes2d::{Bins, Deviation, Levels, Scale};
+use backend::Backend;
+use error::Result;
+use savefig::SaveOptions;
+
+/// Owned version of `Bins`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedBins {
+  Count(usize),
+  Edges(Vec<f64>),
+}
+
+impl<'a> From<&'a Bins<'a>> for OwnedBins {
+  fn from(bins: &'a Bins<'a>) -> OwnedBins {
+    match *bins {
+      Bins::Count(n) => OwnedBins::Count(n),
+      Bins::Edges(edges) => OwnedBins::Edges(edges.to_vec()),
+    }
+  }
+}
+
+/// Owned version of `Deviation`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedDeviation {
+  Symmetric(Vec<f64>),
+  Asymmetric(Vec<f64>, Vec<f64>),
+}
+
+impl<'a> From<&'a Deviation<'a>> for OwnedDeviation {
+  fn from(err: &'a Deviation<'a>) -> OwnedDeviation {
+    match *err {
+      Deviation::Symmetric(err) => OwnedDeviation::Symmetric(err.to_vec()),
+      Deviation::Asymmetric(lower, upper) => {
+        OwnedDeviation::Asymmetric(lower.to_vec(), upper.to_vec())
+      }
+    }
+  }
+}
+
+/// Owned version of `Levels`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedLevels {
+  Count(usize),
+  Values(Vec<f64>),
+}
+
+impl<'a> From<&'a Levels<'a>> for OwnedLevels {
+  fn from(levels: &'a Levels<'a>) -> OwnedLevels {
+    match *levels {
+      Levels::Count(n) => OwnedLevels::Count(n),
+      Levels::Values(values) => OwnedLevels::Values(values.to_vec()),
+    }
+  }
+}
+
+/// A call of `Backend` method, with the copy of its arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+  Figure {
+    figsize: Option<(f64, f64)>,
+    dpi: Option<f64>,
+    facecolor: Option<String>,
+    edgecolor: Option<String>,
+  },
+  Subplot { rows: u32, cols: u32, n: u32 },
+  Suptitle(String),
+  Title {
+    title: String,
+    loc: Option<String>,
+    fontsize: Option<f64>,
+  },
+  XLabel(String),
+  YLabel(String),
+  Grid(bool),
+  Legend(String),
+  XLim((f64, f64)),
+  YLim((f64, f64)),
+  Colorbar {
+    label: Option<String>,
+    orientation: Option<String>,
+    ticks: Option<Vec<f64>>,
+    extend: Option<String>,
+  },
+  XScale(Scale),
+  YScale(Scale),
+  SetStyle(String),
+  SaveFig(String),
+  SaveFigWith(String, SaveOptions),
+  SaveFigBytes(SaveOptions),
+  Show,
+  Plot {
+    xdata: Vec<f64>,
+    ydata: Vec<f64>,
+    label: Option<String>,
+    color: Option<String>,
+    marker: Option<String>,
+    linestyle: Option<String>,
+    linewidth: Option<f64>,
+  },
+  Scatter {
+    xdata: Vec<f64>,
+    ydata: Vec<f64>,
+    label: Option<String>,
+    color: Option<String>,
+    marker: Option<String>,
+  },
+  FillBetween {
+    x: Vec<f64>,
+    y1: Vec<f64>,
+    y2: Vec<f64>,
+    where_: Option<Vec<bool>>,
+    interpolate: bool,
+    step: Option<String>,
+    label: Option<String>,
+  },
+  Bar {
+    x: Vec<f64>,
+    height: Vec<f64>,
+    width: Option<f64>,
+    bottom: Option<Vec<f64>>,
+    horizontal: bool,
+    label: Option<String>,
+    color: Option<String>,
+    edgecolor: Option<String>,
+    align: Option<String>,
+  },
+  Hist {
+    data: Vec<Vec<f64>>,
+    bins: Option<OwnedBins>,
+    weights: Option<Vec<Vec<f64>>>,
+    density: bool,
+    cumulative: bool,
+    histtype: Option<String>,
+    label: Option<String>,
+    color: Option<String>,
+  },
+  ErrorBar {
+    xdata: Vec<f64>,
+    ydata: Vec<f64>,
+    xerr: Option<OwnedDeviation>,
+    yerr: Option<OwnedDeviation>,
+    capsize: Option<f64>,
+    ecolor: Option<String>,
+    label: Option<String>,
+    color: Option<String>,
+    marker: Option<String>,
+    linestyle: Option<String>,
+    linewidth: Option<f64>,
+  },
+  ImShow {
+    data: Vec<f64>,
+    shape: (usize, usize),
+    cmap: Option<String>,
+    vmin: Option<f64>,
+    vmax: Option<f64>,
+    interpolation: Option<String>,
+    origin: Option<String>,
+    extent: Option<(f64, f64, f64, f64)>,
+  },
+  Contour {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z: Vec<f64>,
+    levels: Option<OwnedLevels>,
+    cmap: Option<String>,
+    linewidths: Option<f64>,
+    clabel: bool,
+  },
+  ContourF {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z: Vec<f64>,
+    levels: Option<OwnedLevels>,
+    cmap: Option<String>,
+  },
+  TightLayout,
+}
+
+impl Call {
+  /// the name of called `Backend` method.
+  pub fn method(&self) -> &'static str {
+    match *self {
+      Call::Figure { .. } => "figure",
+      Call::Subplot { .. } => "subplot",
+      Call::Suptitle(_) => "suptitle",
+      Call::Title { .. } => "title",
+      Call::XLabel(_) => "xlabel",
+      Call::YLabel(_) => "ylabel",
+      Call::Grid(_) => "grid",
+      Call::Legend(_) => "legend",
+      Call::XLim(_) => "xlim",
+      Call::YLim(_) => "ylim",
+      Call::Colorbar { .. } => "colorbar",
+      Call::XScale(_) => "xscale",
+      Call::YScale(_) => "yscale",
+      Call::SetStyle(_) => "set_style",
+      Call::SaveFig(_) => "savefig",
+      Call::SaveFigWith(..) => "savefig_with",
+      Call::SaveFigBytes(_) => "savefig_bytes",
+      Call::Show => "show",
+      Call::Plot { .. } => "plot",
+      Call::Scatter { .. } => "scatter",
+      Call::FillBetween { .. } => "fill_between",
+      Call::Bar { .. } => "bar",
+      Call::Hist { .. } => "hist",
+      Call::ErrorBar { .. } => "errorbar",
+      Call::ImShow { .. } => "imshow",
+      Call::Contour { .. } => "contour",
+      Call::ContourF { .. } => "contourf",
+      Call::TightLayout => "tight_layout",
+    }
+  }
+}
+
+
+/// Records the calls of `Backend` methods, instead of drawing anything.
+///
+/// It is intended to check what a `Figure` or an `Axes2D` does, without Python.
+#[derive(Debug, Default, Clone)]
+pub struct Recorder {
+  calls: Vec<Call>,
+}
+
+impl Recorder {
+  pub fn new() -> Recorder {
+    Recorder::default()
+  }
+
+  /// get the recorded calls, in order of invocation.
+  pub fn calls(&self) -> &[Call] {
+    &self.calls
+  }
+
+  /// get the names of recorded methods, in order of invocation.
+  pub fn methods(&self) -> Vec<&'static str> {
+    self.calls.iter().map(Call::method).collect()
+  }
+
+  /// remove all recorded calls.
+  pub fn clear(&mut self) {
+    self.calls.clear();
+  }
+
+  fn record(&mut self, call: Call) -> Result<&mut Self> {
+    self.calls.push(call);
+    Ok(self)
+  }
+}
+
+impl Backend for Recorder {
+  fn figure(&mut self,
+            figsize: &Option<(f64, f64)>,
+            dpi: &Option<f64>,
+            facecolor: &Option<String>,
+            edgecolor: &Option<String>)
+            -> Result<&mut Self> {
+    self.record(Call::Figure {
+                  figsize: *figsize,
+                  dpi: *dpi,
+                  facecolor: facecolor.clone(),
+                  edgecolor: edgecolor.clone(),
+                })
+  }
+
+  fn subplot(&mut self, rows: u32, cols: u32, n: u32) -> Result<&mut Self> {
+    self.record(Call::Subplot { rows, cols, n })
+  }
+
+  fn suptitle(&mut self, suptitle: &str) -> Result<&mut Self> {
+    self.record(Call::Suptitle(suptitle.to_owned()))
+  }
+
+  fn title(&mut self,
+           title: &str,
+           loc: &Option<String>,
+           fontsize: &Option<f64>)
+           -> Result<&mut Self> {
+    self.record(Call::Title {
+                  title: title.to_owned(),
+                  loc: loc.clone(),
+                  fontsize: *fontsize,
+                })
+  }
+
+  fn xlabel(&mut self, xlabel: &str) -> Result<&mut Self> {
+    self.record(Call::XLabel(xlabel.to_owned()))
+  }
+
+  fn ylabel(&mut self, ylabel: &str) -> Result<&mut Self> {
+    self.record(Call::YLabel(ylabel.to_owned()))
+  }
+
+  fn grid(&mut self, grid: bool) -> Result<&mut Self> {
+    self.record(Call::Grid(grid))
+  }
+
+  fn legend(&mut self, loc: &str) -> Result<&mut Self> {
+    self.record(Call::Legend(loc.to_owned()))
+  }
+
+  fn xlim(&mut self, xlim: &(f64, f64)) -> Result<&mut Self> {
+    self.record(Call::XLim(*xlim))
+  }
+
+  fn ylim(&mut self, ylim: &(f64, f64)) -> Result<&mut Self> {
+    self.record(Call::YLim(*ylim))
+  }
+
+  fn colorbar(&mut self,
+              label: &Option<String>,
+              orientation: &Option<String>,
+              ticks: &Option<&[f64]>,
+              extend: &Option<String>)
+              -> Result<&mut Self> {
+    self.record(Call::Colorbar {
+                  label: label.clone(),
+                  orientation: orientation.clone(),
+                  ticks: ticks.map(|t| t.to_vec()),
+                  extend: extend.clone(),
+                })
+  }
+
+  fn xscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.record(Call::XScale(*scale))
+  }
+
+  fn yscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.record(Call::YScale(*scale))
+  }
+
+  fn set_style(&mut self, stylename: &str) -> Result<&mut Self> {
+    self.record(Call::SetStyle(stylename.to_owned()))
+  }
+
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.record(Call::SaveFig(filename.to_owned()))
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    self.record(Call::SaveFigWith(filename.to_owned(), options.clone()))
+  }
+
+  /// records the call, and returns an empty buffer.
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    self.record(Call::SaveFigBytes(options.clone()))?;
+    Ok(Vec::new())
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    self.record(Call::Show)
+  }
+
+  fn plot(&mut self,
+          xdata: &[f64],
+          ydata: &[f64],
+          label: &Option<String>,
+          color: &Option<String>,
+          marker: &Option<String>,
+          linestyle: &Option<String>,
+          linewidth: &Option<f64>)
+          -> Result<&mut Self> {
+    self.record(Call::Plot {
+                  xdata: xdata.to_vec(),
+                  ydata: ydata.to_vec(),
+                  label: label.clone(),
+                  color: color.clone(),
+                  marker: marker.clone(),
+                  linestyle: linestyle.clone(),
+                  linewidth: *linewidth,
+                })
+  }
+
+  fn scatter(&mut self,
+             xdata: &[f64],
+             ydata: &[f64],
+             label: &Option<String>,
+             color: &Option<String>,
+             marker: &Option<String>)
+             -> Result<&mut Self> {
+    self.record(Call::Scatter {
+                  xdata: xdata.to_vec(),
+                  ydata: ydata.to_vec(),
+                  label: label.clone(),
+                  color: color.clone(),
+                  marker: marker.clone(),
+                })
+  }
+
+  fn fill_between(&mut self,
+                  x: &[f64],
+                  y1: &[f64],
+                  y2: &[f64],
+                  where_: &Option<&[bool]>,
+                  interpolate: bool,
+                  step: &Option<String>,
+                  label: &Option<String>)
+                  -> Result<&mut Self> {
+    self.record(Call::FillBetween {
+                  x: x.to_vec(),
+                  y1: y1.to_vec(),
+                  y2: y2.to_vec(),
+                  where_: where_.map(|w| w.to_vec()),
+                  interpolate,
+                  step: step.clone(),
+                  label: label.clone(),
+                })
+  }
+
+  fn bar(&mut self,
+         x: &[f64],
+         height: &[f64],
+         width: &Option<f64>,
+         bottom: &Option<&[f64]>,
+         horizontal: bool,
+         label: &Option<String>,
+         color: &Option<String>,
+         edgecolor: &Option<String>,
+         align: &Option<String>)
+         -> Result<&mut Self> {
+    self.record(Call::Bar {
+                  x: x.to_vec(),
+                  height: height.to_vec(),
+                  width: *width,
+                  bottom: bottom.map(|b| b.to_vec()),
+                  horizontal,
+                  label: label.clone(),
+                  color: color.clone(),
+                  edgecolor: edgecolor.clone(),
+                  align: align.clone(),
+                })
+  }
+
+  fn hist(&mut self,
+          data: &[&[f64]],
+          bins: &Option<Bins>,
+          weights: &Option<&[&[f64]]>,
+          density: bool,
+          cumulative: bool,
+          histtype: &Option<String>,
+          label: &Option<String>,
+          color: &Option<String>)
+          -> Result<&mut Self> {
+    self.record(Call::Hist {
+                  data: data.iter().map(|d| d.to_vec()).collect(),
+                  bins: bins.as_ref().map(OwnedBins::from),
+                  weights: weights.map(|w| w.iter().map(|w| w.to_vec()).collect()),
+                  density,
+                  cumulative,
+                  histtype: histtype.clone(),
+                  label: label.clone(),
+                  color: color.clone(),
+                })
+  }
+
+  fn errorbar(&mut self,
+              xdata: &[f64],
+              ydata: &[f64],
+              xerr: &Option<Deviation>,
+              yerr: &Option<Deviation>,
+              capsize: &Option<f64>,
+              ecolor: &Option<String>,
+              label: &Option<String>,
+              color: &Option<String>,
+              marker: &Option<String>,
+              linestyle: &Option<String>,
+              linewidth: &Option<f64>)
+              -> Result<&mut Self> {
+    self.record(Call::ErrorBar {
+                  xdata: xdata.to_vec(),
+                  ydata: ydata.to_vec(),
+                  xerr: xerr.as_ref().map(OwnedDeviation::from),
+                  yerr: yerr.as_ref().map(OwnedDeviation::from),
+                  capsize: *capsize,
+                  ecolor: ecolor.clone(),
+                  label: label.clone(),
+                  color: color.clone(),
+                  marker: marker.clone(),
+                  linestyle: linestyle.clone(),
+                  linewidth: *linewidth,
+                })
+  }
+
+  fn imshow(&mut self,
+            data: &[f64],
+            shape: (usize, usize),
+            cmap: &Option<String>,
+            vmin: &Option<f64>,
+            vmax: &Option<f64>,
+            interpolation: &Option<String>,
+            origin: &Option<String>,
+            extent: &Option<(f64, f64, f64, f64)>)
+            -> Result<&mut Self> {
+    self.record(Call::ImShow {
+                  data: data.to_vec(),
+                  shape,
+                  cmap: cmap.clone(),
+                  vmin: *vmin,
+                  vmax: *vmax,
+                  interpolation: interpolation.clone(),
+                  origin: origin.clone(),
+                  extent: *extent,
+                })
+  }
+
+  fn contour(&mut self,
+             x: &[f64],
+             y: &[f64],
+             z: &[f64],
+             levels: &Option<Levels>,
+             cmap: &Option<String>,
+             linewidths: &Option<f64>,
+             clabel: bool)
+             -> Result<&mut Self> {
+    self.record(Call::Contour {
+                  x: x.to_vec(),
+                  y: y.to_vec(),
+                  z: z.to_vec(),
+                  levels: levels.as_ref().map(OwnedLevels::from),
+                  cmap: cmap.clone(),
+                  linewidths: *linewidths,
+                  clabel,
+                })
+  }
+
+  fn contourf(&mut self,
+              x: &[f64],
+              y: &[f64],
+              z: &[f64],
+              levels: &Option<Levels>,
+              cmap: &Option<String>)
+              -> Result<&mut Self> {
+    self.record(Call::ContourF {
+                  x: x.to_vec(),
+                  y: y.to_vec(),
+                  z: z.to_vec(),
+                  levels: levels.as_ref().map(OwnedLevels::from),
+                  cmap: cmap.clone(),
+                })
+  }
+
+  fn tight_layout(&mut self) -> Result<&mut Self> {
+    self.record(Call::TightLayout)
+  }
+}
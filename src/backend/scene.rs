@@ -0,0 +1,1047 @@
+//! The description of a figure, collected from the calls of `Backend` methods.
+//!
+//! The backends which draw figures by themselves (instead of delegating to matplotlib) build a
+//! `Scene` first, and then render it at once when the figure is saved.
+
+use axes2d::{Bins, Deviation, Levels, Scale};
+use backend::Backend;
+use error::{Error, Result};
+use savefig::SaveOptions;
+
+/// the default size of a figure in inches, same as matplotlib's one.
+pub const DEFAULT_FIGSIZE: (f64, f64) = (6.4, 4.8);
+
+/// the default resolution of a figure, same as matplotlib's one.
+pub const DEFAULT_DPI: f64 = 100.0;
+
+/// the default color cycle of matplotlib ("tab10").
+const COLOR_CYCLE: [&str; 10] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b",
+                                 "#e377c2", "#7f7f7f", "#bcbd22", "#17becf"];
+
+/// Implements the methods of `Backend` which only describe the figure, by forwarding them to
+/// the field `scene`.
+///
+/// the remaining methods (`savefig`, `savefig_with`, `savefig_bytes` and `show`) have to be
+/// implemented by the backend.
+macro_rules! delegate_to_scene {
+  () => {
+    delegate_to_scene! {
+      figure(figsize: &Option<(f64, f64)>,
+             dpi: &Option<f64>,
+             facecolor: &Option<String>,
+             edgecolor: &Option<String>);
+      subplot(rows: u32, cols: u32, n: u32);
+      suptitle(suptitle: &str);
+      title(title: &str, loc: &Option<String>, fontsize: &Option<f64>);
+      xlabel(xlabel: &str);
+      ylabel(ylabel: &str);
+      grid(grid: bool);
+      legend(loc: &str);
+      xlim(xlim: &(f64, f64));
+      ylim(ylim: &(f64, f64));
+      colorbar(label: &Option<String>,
+               orientation: &Option<String>,
+               ticks: &Option<&[f64]>,
+               extend: &Option<String>);
+      xscale(scale: &$crate::axes2d::Scale);
+      yscale(scale: &$crate::axes2d::Scale);
+      set_style(stylename: &str);
+      plot(xdata: &[f64],
+           ydata: &[f64],
+           label: &Option<String>,
+           color: &Option<String>,
+           marker: &Option<String>,
+           linestyle: &Option<String>,
+           linewidth: &Option<f64>);
+      scatter(xdata: &[f64],
+              ydata: &[f64],
+              label: &Option<String>,
+              color: &Option<String>,
+              marker: &Option<String>);
+      fill_between(x: &[f64],
+                   y1: &[f64],
+                   y2: &[f64],
+                   where_: &Option<&[bool]>,
+                   interpolate: bool,
+                   step: &Option<String>,
+                   label: &Option<String>);
+      bar(x: &[f64],
+          height: &[f64],
+          width: &Option<f64>,
+          bottom: &Option<&[f64]>,
+          horizontal: bool,
+          label: &Option<String>,
+          color: &Option<String>,
+          edgecolor: &Option<String>,
+          align: &Option<String>);
+      hist(data: &[&[f64]],
+           bins: &Option<$crate::axes2d::Bins>,
+           weights: &Option<&[&[f64]]>,
+           density: bool,
+           cumulative: bool,
+           histtype: &Option<String>,
+           label: &Option<String>,
+           color: &Option<String>);
+      errorbar(xdata: &[f64],
+               ydata: &[f64],
+               xerr: &Option<$crate::axes2d::Deviation>,
+               yerr: &Option<$crate::axes2d::Deviation>,
+               capsize: &Option<f64>,
+               ecolor: &Option<String>,
+               label: &Option<String>,
+               color: &Option<String>,
+               marker: &Option<String>,
+               linestyle: &Option<String>,
+               linewidth: &Option<f64>);
+      imshow(data: &[f64],
+             shape: (usize, usize),
+             cmap: &Option<String>,
+             vmin: &Option<f64>,
+             vmax: &Option<f64>,
+             interpolation: &Option<String>,
+             origin: &Option<String>,
+             extent: &Option<(f64, f64, f64, f64)>);
+      contour(x: &[f64],
+              y: &[f64],
+              z: &[f64],
+              levels: &Option<$crate::axes2d::Levels>,
+              cmap: &Option<String>,
+              linewidths: &Option<f64>,
+              clabel: bool);
+      contourf(x: &[f64],
+               y: &[f64],
+               z: &[f64],
+               levels: &Option<$crate::axes2d::Levels>,
+               cmap: &Option<String>);
+      tight_layout();
+    }
+  };
+  ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+    $(
+      fn $name(&mut self, $($arg: $ty),*) -> $crate::error::Result<&mut Self> {
+        self.scene.$name($($arg),*)?;
+        Ok(self)
+      }
+    )*
+  };
+}
+
+
+/// A figure, which consists of some axes.
+#[derive(Debug, Default, Clone)]
+pub struct Scene {
+  pub figsize: Option<(f64, f64)>,
+  pub dpi: Option<f64>,
+  pub facecolor: Option<String>,
+  #[cfg_attr(not(feature = "svg"), allow(dead_code))]
+  pub edgecolor: Option<String>,
+  pub suptitle: Option<String>,
+  pub axes: Vec<Axes>,
+  current: Option<usize>,
+}
+
+/// An axes placed in the grid of subplots.
+#[derive(Debug, Clone)]
+pub struct Axes {
+  pub rows: u32,
+  pub cols: u32,
+  /// the position in the grid, starting from 1 (same as `plt.subplot()`).
+  pub n: u32,
+  pub title: Option<String>,
+  pub title_loc: Option<String>,
+  pub xlabel: Option<String>,
+  pub ylabel: Option<String>,
+  pub grid: bool,
+  pub legend: Option<String>,
+  pub xlim: Option<(f64, f64)>,
+  pub ylim: Option<(f64, f64)>,
+  pub xscale: Scale,
+  pub yscale: Scale,
+  pub series: Vec<Series>,
+}
+
+/// A data series drawn in an axes.
+///
+/// the colors are already resolved, as a hex code or a CSS color name.
+#[derive(Debug, Clone)]
+pub enum Series {
+  Line {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    label: Option<String>,
+    color: String,
+    marker: Option<String>,
+    linestyle: Option<String>,
+    linewidth: Option<f64>,
+  },
+  Scatter {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    label: Option<String>,
+    color: String,
+    marker: Option<String>,
+  },
+  /// the area between `y1` and `y2`, which is split at the points where `where_` is false.
+  Area {
+    x: Vec<f64>,
+    y1: Vec<f64>,
+    y2: Vec<f64>,
+    where_: Option<Vec<bool>>,
+    label: Option<String>,
+    color: String,
+  },
+}
+
+impl Scene {
+  /// the size of the figure in inches.
+  pub fn figsize(&self) -> (f64, f64) {
+    self.figsize.unwrap_or(DEFAULT_FIGSIZE)
+  }
+
+  /// the resolution of the figure, in dots per inch.
+  pub fn dpi(&self) -> f64 {
+    self.dpi.unwrap_or(DEFAULT_DPI)
+  }
+
+  fn current_axes(&mut self) -> &mut Axes {
+    let index = match self.current {
+      Some(index) => index,
+      None => {
+        // same as matplotlib, which creates a single subplot implicitly.
+        self.axes.push(Axes::new(1, 1, 1));
+        self.axes.len() - 1
+      }
+    };
+    self.current = Some(index);
+    &mut self.axes[index]
+  }
+
+  fn push_series(&mut self, series: Series) -> Result<&mut Self> {
+    self.current_axes().series.push(series);
+    Ok(self)
+  }
+
+  fn next_color(&mut self, color: &Option<String>) -> String {
+    let axes = self.current_axes();
+    match *color {
+      Some(ref color) => resolve_color(color),
+      None => COLOR_CYCLE[axes.series.len() % COLOR_CYCLE.len()].to_owned(),
+    }
+  }
+}
+
+impl Axes {
+  pub fn new(rows: u32, cols: u32, n: u32) -> Axes {
+    Axes {
+      rows,
+      cols,
+      n,
+      title: None,
+      title_loc: None,
+      xlabel: None,
+      ylabel: None,
+      grid: false,
+      legend: None,
+      xlim: None,
+      ylim: None,
+      xscale: Scale::Linear,
+      yscale: Scale::Linear,
+      series: Vec::new(),
+    }
+  }
+
+  /// the row and column of this axes in the grid, starting from 0.
+  pub fn cell(&self) -> (u32, u32) {
+    let index = self.n.saturating_sub(1);
+    (index / self.cols.max(1), index % self.cols.max(1))
+  }
+
+  /// the range of x axis, which is `xlim` or the range of data with margins.
+  pub fn xrange(&self) -> (f64, f64) {
+    match self.xlim {
+      Some((lo, hi)) if lo == hi => expand(lo),
+      Some(xlim) => xlim,
+      None => autoscale(self.series.iter().flat_map(|s| s.xvalues())),
+    }
+  }
+
+  /// the range of y axis, which is `ylim` or the range of data with margins.
+  pub fn yrange(&self) -> (f64, f64) {
+    match self.ylim {
+      Some((lo, hi)) if lo == hi => expand(lo),
+      Some(ylim) => ylim,
+      None => autoscale(self.series.iter().flat_map(|s| s.yvalues())),
+    }
+  }
+
+  /// the series which are shown in the legend.
+  pub fn labeled_series(&self) -> Vec<&Series> {
+    self.series.iter().filter(|s| s.label().is_some()).collect()
+  }
+}
+
+impl Series {
+  pub fn label(&self) -> Option<&str> {
+    match *self {
+      Series::Line { ref label, .. } |
+      Series::Scatter { ref label, .. } |
+      Series::Area { ref label, .. } => {
+        // same as matplotlib, empty labels and the ones starting with '_' are not shown.
+        label.as_deref().filter(|l| !l.is_empty() && !l.starts_with('_'))
+      }
+    }
+  }
+
+  pub fn color(&self) -> &str {
+    match *self {
+      Series::Line { ref color, .. } |
+      Series::Scatter { ref color, .. } |
+      Series::Area { ref color, .. } => color,
+    }
+  }
+
+  fn xvalues<'a>(&'a self) -> Box<dyn Iterator<Item = f64> + 'a> {
+    match *self {
+      Series::Line { ref x, .. } |
+      Series::Scatter { ref x, .. } |
+      Series::Area { ref x, .. } => Box::new(x.iter().cloned()),
+    }
+  }
+
+  fn yvalues<'a>(&'a self) -> Box<dyn Iterator<Item = f64> + 'a> {
+    match *self {
+      Series::Line { ref y, .. } |
+      Series::Scatter { ref y, .. } => Box::new(y.iter().cloned()),
+      Series::Area { ref y1, ref y2, .. } => Box::new(y1.iter().chain(y2).cloned()),
+    }
+  }
+}
+
+
+/// compute the range of finite values, with 5% margins (same as matplotlib).
+fn autoscale<I: Iterator<Item = f64>>(values: I) -> (f64, f64) {
+  let (lo, hi) = values.filter(|v| v.is_finite())
+    .fold((f64::INFINITY, f64::NEG_INFINITY),
+          |(lo, hi), v| (lo.min(v), hi.max(v)));
+  if lo > hi {
+    (0.0, 1.0)
+  } else if lo == hi {
+    expand(lo)
+  } else {
+    let margin = (hi - lo) * 0.05;
+    (lo - margin, hi + margin)
+  }
+}
+
+/// expand an empty range at `value`, in the same way as matplotlib.
+fn expand(value: f64) -> (f64, f64) {
+  let delta = if value == 0.0 { 0.5 } else { value.abs() * 0.05 };
+  (value - delta, value + delta)
+}
+
+/// Locations of ticks on an axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ticks {
+  pub values: Vec<f64>,
+  /// the interval between ticks.
+  pub step: f64,
+}
+
+impl Ticks {
+  /// choose "nice" tick locations (multiples of 1, 2, 2.5 or 5 times a power of 10) within
+  /// `range`, at most `max_ticks` of them.
+  pub fn new(range: (f64, f64), max_ticks: usize) -> Ticks {
+    let (lo, hi) = if range.0 <= range.1 {
+      range
+    } else {
+      (range.1, range.0)
+    };
+    let span = hi - lo;
+    if span.is_nan() || span <= 0.0 || span.is_infinite() {
+      return Ticks {
+        values: vec![lo],
+        step: 1.0,
+      };
+    }
+    let raw = span / max_ticks.max(1) as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0]
+      .iter()
+      .map(|m| m * magnitude)
+      .find(|&step| step >= raw)
+      .unwrap_or(10.0 * magnitude);
+    let start = (lo / step - 1e-9).ceil() as i64;
+    let end = (hi / step + 1e-9).floor() as i64;
+    // adding 0.0 turns -0.0 into 0.0.
+    let values = (start..end + 1).map(|i| i as f64 * step + 0.0).collect();
+    Ticks { values, step }
+  }
+
+  /// format a tick value, with the number of decimals enough to distinguish ticks.
+  pub fn format(&self, value: f64) -> String {
+    let decimals = (0..10)
+      .find(|&d| {
+        let scaled = self.step * 10f64.powi(d as i32);
+        (scaled - scaled.round()).abs() < 1e-6 * scaled.max(1.0)
+      })
+      .unwrap_or(10);
+    format!("{:.*}", decimals, value)
+  }
+}
+
+
+/// Horizontal or vertical position of a box inside the axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+  Start,
+  Center,
+  End,
+}
+
+/// parse the location of legend in matplotlib's notation (e.g. `"upper right"`),
+/// into the horizontal and vertical positions.
+///
+/// `Start` means left or bottom. `"best"` and unknown locations are treated as `"upper right"`.
+pub fn legend_position(loc: &str) -> (Align, Align) {
+  let loc = match loc.trim() {
+    "1" => "upper right",
+    "2" => "upper left",
+    "3" => "lower left",
+    "4" => "lower right",
+    "5" => "right",
+    "6" => "center left",
+    "7" => "center right",
+    "8" => "lower center",
+    "9" => "upper center",
+    "10" => "center",
+    loc => loc,
+  };
+  let mut horizontal = Align::End;
+  let mut vertical = Align::End;
+  match loc {
+    "center" => return (Align::Center, Align::Center),
+    "right" => return (Align::End, Align::Center),
+    _ => (),
+  }
+  for word in loc.split_whitespace() {
+    match word {
+      "upper" => vertical = Align::End,
+      "lower" => vertical = Align::Start,
+      "left" => horizontal = Align::Start,
+      "right" => horizontal = Align::End,
+      "center" if loc.starts_with("center") => vertical = Align::Center,
+      "center" => horizontal = Align::Center,
+      _ => (),
+    }
+  }
+  (horizontal, vertical)
+}
+
+
+/// resolve matplotlib's color notations (`"C0"`, `"r"`, `"tab:blue"`, ...) into a hex code.
+///
+/// the other notations (hex codes and color names) are returned as they are.
+pub fn resolve_color(color: &str) -> String {
+  let color = color.trim();
+  let resolved = match color {
+    "b" => "#0000ff",
+    "g" => "#008000",
+    "r" => "#ff0000",
+    "c" => "#00bfbf",
+    "m" => "#bf00bf",
+    "y" => "#bfbf00",
+    "k" => "#000000",
+    "w" => "#ffffff",
+    "tab:blue" => COLOR_CYCLE[0],
+    "tab:orange" => COLOR_CYCLE[1],
+    "tab:green" => COLOR_CYCLE[2],
+    "tab:red" => COLOR_CYCLE[3],
+    "tab:purple" => COLOR_CYCLE[4],
+    "tab:brown" => COLOR_CYCLE[5],
+    "tab:pink" => COLOR_CYCLE[6],
+    "tab:gray" | "tab:grey" => COLOR_CYCLE[7],
+    "tab:olive" => COLOR_CYCLE[8],
+    "tab:cyan" => COLOR_CYCLE[9],
+    _ => {
+      if color.len() == 2 && color.starts_with('C') {
+        if let Ok(i) = color[1..].parse::<usize>() {
+          return COLOR_CYCLE[i].to_owned();
+        }
+      }
+      color
+    }
+  };
+  resolved.to_owned()
+}
+
+/// convert a color (a hex code or a basic color name) into RGB components.
+pub fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+  let color = resolve_color(color);
+  if let Some(hex) = color.strip_prefix('#') {
+    let digit = |i: usize, n: usize| u8::from_str_radix(hex.get(i..i + n)?, 16).ok();
+    return match hex.len() {
+      3 => Some((digit(0, 1)? * 17, digit(1, 1)? * 17, digit(2, 1)? * 17)),
+      6 | 8 => Some((digit(0, 2)?, digit(2, 2)?, digit(4, 2)?)),
+      _ => None,
+    };
+  }
+  let rgb = match &color.to_lowercase()[..] {
+    "black" => (0, 0, 0),
+    "white" => (255, 255, 255),
+    "red" => (255, 0, 0),
+    "green" => (0, 128, 0),
+    "blue" => (0, 0, 255),
+    "cyan" => (0, 255, 255),
+    "magenta" => (255, 0, 255),
+    "yellow" => (255, 255, 0),
+    "orange" => (255, 165, 0),
+    "purple" => (128, 0, 128),
+    "brown" => (165, 42, 42),
+    "pink" => (255, 192, 203),
+    "gray" | "grey" => (128, 128, 128),
+    "navy" => (0, 0, 128),
+    "olive" => (128, 128, 0),
+    "teal" => (0, 128, 128),
+    "maroon" => (128, 0, 0),
+    "lime" => (0, 255, 0),
+    _ => return None,
+  };
+  Some(rgb)
+}
+
+/// split a polyline into the segments of finite points.
+pub fn segments(x: &[f64], y: &[f64]) -> Vec<Vec<(f64, f64)>> {
+  let mut segments = Vec::new();
+  let mut current = Vec::new();
+  for (&x, &y) in x.iter().zip(y) {
+    if x.is_finite() && y.is_finite() {
+      current.push((x, y));
+    } else if !current.is_empty() {
+      segments.push(current);
+      current = Vec::new();
+    }
+  }
+  if !current.is_empty() {
+    segments.push(current);
+  }
+  segments
+}
+
+/// split the area between `y1` and `y2` into the runs where `where_` is true and all values
+/// are finite, as the lists of `(x, y1, y2)`.
+pub fn area_runs(x: &[f64],
+                 y1: &[f64],
+                 y2: &[f64],
+                 where_: &Option<Vec<bool>>)
+                 -> Vec<Vec<(f64, f64, f64)>> {
+  let mut runs = Vec::new();
+  let mut current = Vec::new();
+  for (i, ((&x, &y1), &y2)) in x.iter().zip(y1).zip(y2).enumerate() {
+    let enabled = where_.as_ref().and_then(|w| w.get(i).cloned()).unwrap_or(true);
+    if enabled && x.is_finite() && y1.is_finite() && y2.is_finite() {
+      current.push((x, y1, y2));
+    } else if !current.is_empty() {
+      runs.push(current);
+      current = Vec::new();
+    }
+  }
+  if !current.is_empty() {
+    runs.push(current);
+  }
+  runs
+}
+
+/// convert the line style into the dash pattern in points, scaled by the line width
+/// (empty for solid lines).
+///
+/// returns `None` if the line is not drawn.
+pub fn dash_pattern(linestyle: &Option<String>, linewidth: f64) -> Result<Option<Vec<f64>>> {
+  let pattern: &[f64] = match linestyle.as_deref() {
+    None | Some("-") | Some("solid") => &[],
+    Some("--") | Some("dashed") => &[3.7, 1.6],
+    Some(":") | Some("dotted") => &[1.0, 1.65],
+    Some("-.") | Some("dashdot") => &[6.4, 1.6, 1.0, 1.6],
+    Some("") | Some(" ") | Some("None") | Some("none") => return Ok(None),
+    Some(style) => return Err(Error::InvalidInput(format!("unknown line style: {}", style))),
+  };
+  Ok(Some(pattern.iter().map(|p| p * linewidth).collect()))
+}
+
+/// format a number with two decimals, without trailing zeros (for coordinates and lengths).
+pub fn fixed(value: f64) -> String {
+  let s = format!("{:.2}", value);
+  let s = s.trim_end_matches('0').trim_end_matches('.');
+  if s == "-0" { "0".to_owned() } else { s.to_owned() }
+}
+
+/// format a number in full precision, in the notation which gnuplot and pgfplots can read.
+///
+/// large and small magnitudes are written in the exponential notation.
+pub fn number(value: f64) -> String {
+  if value.is_nan() {
+    "nan".to_owned()
+  } else if value.is_infinite() {
+    if value > 0.0 { "inf" } else { "-inf" }.to_owned()
+  } else if value != 0.0 && (value.abs() >= 1e6 || value.abs() < 1e-4) {
+    format!("{:e}", value)
+  } else {
+    // adding 0.0 turns -0.0 into 0.0.
+    format!("{}", value + 0.0)
+  }
+}
+
+/// escape the special characters in XML and HTML.
+pub fn escape(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped += "&amp;",
+      '<' => escaped += "&lt;",
+      '>' => escaped += "&gt;",
+      '"' => escaped += "&quot;",
+      '\'' => escaped += "&#39;",
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+fn unsupported<T>(method: &str) -> Result<T> {
+  Err(Error::Unsupported(format!("`{}` is not supported by this backend", method)))
+}
+
+fn step_points(x: &[f64], ys: &[&[f64]], step: &str) -> Result<(Vec<f64>, Vec<Vec<f64>>)> {
+  let n = x.len();
+  if let Some(y) = ys.iter().find(|y| y.len() != n) {
+    return Err(Error::InvalidInput(format!("the length of y ({}) does not match to the length \
+                                            of x ({})",
+                                           y.len(),
+                                           n)));
+  }
+  let mut sx = Vec::with_capacity(2 * n);
+  let mut sys = vec![Vec::with_capacity(2 * n); ys.len()];
+  for i in 0..n {
+    let (x0, x1) = match step {
+      "pre" => (if i > 0 { x[i - 1] } else { x[i] }, x[i]),
+      "post" => (x[i], if i + 1 < n { x[i + 1] } else { x[i] }),
+      "mid" => {
+        (if i > 0 { (x[i - 1] + x[i]) / 2.0 } else { x[i] },
+         if i + 1 < n { (x[i] + x[i + 1]) / 2.0 } else { x[i] })
+      }
+      _ => return Err(Error::InvalidInput(format!("invalid step: {}", step))),
+    };
+    sx.push(x0);
+    sx.push(x1);
+    for (sy, y) in sys.iter_mut().zip(ys) {
+      sy.push(y[i]);
+      sy.push(y[i]);
+    }
+  }
+  Ok((sx, sys))
+}
+
+
+impl Backend for Scene {
+  fn figure(&mut self,
+            figsize: &Option<(f64, f64)>,
+            dpi: &Option<f64>,
+            facecolor: &Option<String>,
+            edgecolor: &Option<String>)
+            -> Result<&mut Self> {
+    *self = Scene {
+      figsize: *figsize,
+      dpi: *dpi,
+      facecolor: facecolor.as_ref().map(|c| resolve_color(c)),
+      edgecolor: edgecolor.as_ref().map(|c| resolve_color(c)),
+      ..Scene::default()
+    };
+    Ok(self)
+  }
+
+  fn subplot(&mut self, rows: u32, cols: u32, n: u32) -> Result<&mut Self> {
+    if rows == 0 || cols == 0 || n == 0 || n > rows * cols {
+      return Err(Error::InvalidInput(format!("invalid subplot: ({}, {}, {})", rows, cols, n)));
+    }
+    let found = self.axes.iter().position(|a| a.rows == rows && a.cols == cols && a.n == n);
+    self.current = match found {
+      Some(index) => Some(index),
+      None => {
+        self.axes.push(Axes::new(rows, cols, n));
+        Some(self.axes.len() - 1)
+      }
+    };
+    Ok(self)
+  }
+
+  fn suptitle(&mut self, suptitle: &str) -> Result<&mut Self> {
+    self.suptitle = Some(suptitle.to_owned());
+    Ok(self)
+  }
+
+  fn title(&mut self,
+           title: &str,
+           loc: &Option<String>,
+           _fontsize: &Option<f64>)
+           -> Result<&mut Self> {
+    let axes = self.current_axes();
+    axes.title = Some(title.to_owned());
+    axes.title_loc = loc.clone();
+    Ok(self)
+  }
+
+  fn xlabel(&mut self, xlabel: &str) -> Result<&mut Self> {
+    self.current_axes().xlabel = Some(xlabel.to_owned());
+    Ok(self)
+  }
+
+  fn ylabel(&mut self, ylabel: &str) -> Result<&mut Self> {
+    self.current_axes().ylabel = Some(ylabel.to_owned());
+    Ok(self)
+  }
+
+  fn grid(&mut self, grid: bool) -> Result<&mut Self> {
+    self.current_axes().grid = grid;
+    Ok(self)
+  }
+
+  fn legend(&mut self, loc: &str) -> Result<&mut Self> {
+    self.current_axes().legend = Some(loc.to_owned());
+    Ok(self)
+  }
+
+  fn xlim(&mut self, xlim: &(f64, f64)) -> Result<&mut Self> {
+    self.current_axes().xlim = Some(*xlim);
+    Ok(self)
+  }
+
+  fn ylim(&mut self, ylim: &(f64, f64)) -> Result<&mut Self> {
+    self.current_axes().ylim = Some(*ylim);
+    Ok(self)
+  }
+
+  fn colorbar(&mut self,
+              _label: &Option<String>,
+              _orientation: &Option<String>,
+              _ticks: &Option<&[f64]>,
+              _extend: &Option<String>)
+              -> Result<&mut Self> {
+    unsupported("colorbar")
+  }
+
+  fn xscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.current_axes().xscale = *scale;
+    Ok(self)
+  }
+
+  fn yscale(&mut self, scale: &Scale) -> Result<&mut Self> {
+    self.current_axes().yscale = *scale;
+    Ok(self)
+  }
+
+  fn set_style(&mut self, _stylename: &str) -> Result<&mut Self> {
+    unsupported("set_style")
+  }
+
+  fn savefig(&mut self, _filename: &str) -> Result<&mut Self> {
+    unsupported("savefig")
+  }
+
+  fn savefig_with(&mut self, _filename: &str, _options: &SaveOptions) -> Result<&mut Self> {
+    unsupported("savefig_with")
+  }
+
+  fn savefig_bytes(&mut self, _options: &SaveOptions) -> Result<Vec<u8>> {
+    unsupported("savefig_bytes")
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    unsupported("show")
+  }
+
+  fn plot(&mut self,
+          xdata: &[f64],
+          ydata: &[f64],
+          label: &Option<String>,
+          color: &Option<String>,
+          marker: &Option<String>,
+          linestyle: &Option<String>,
+          linewidth: &Option<f64>)
+          -> Result<&mut Self> {
+    let color = self.next_color(color);
+    self.push_series(Series::Line {
+                       x: xdata.to_vec(),
+                       y: ydata.to_vec(),
+                       label: label.clone(),
+                       color,
+                       marker: marker.clone(),
+                       linestyle: linestyle.clone(),
+                       linewidth: *linewidth,
+                     })
+  }
+
+  fn scatter(&mut self,
+             xdata: &[f64],
+             ydata: &[f64],
+             label: &Option<String>,
+             color: &Option<String>,
+             marker: &Option<String>)
+             -> Result<&mut Self> {
+    let color = self.next_color(color);
+    self.push_series(Series::Scatter {
+                       x: xdata.to_vec(),
+                       y: ydata.to_vec(),
+                       label: label.clone(),
+                       color,
+                       marker: marker.clone(),
+                     })
+  }
+
+  fn fill_between(&mut self,
+                  x: &[f64],
+                  y1: &[f64],
+                  y2: &[f64],
+                  where_: &Option<&[bool]>,
+                  _interpolate: bool,
+                  step: &Option<String>,
+                  label: &Option<String>)
+                  -> Result<&mut Self> {
+    let color = self.next_color(&None);
+    let series = match *step {
+      Some(ref step) => {
+        let (sx, mut sys) = step_points(x, &[y1, y2], step)?;
+        let sy2 = sys.pop().unwrap_or_default();
+        let sy1 = sys.pop().unwrap_or_default();
+        Series::Area {
+          x: sx,
+          y1: sy1,
+          y2: sy2,
+          where_: where_.map(|w| w.iter().flat_map(|&b| vec![b, b]).collect()),
+          label: label.clone(),
+          color,
+        }
+      }
+      None => {
+        Series::Area {
+          x: x.to_vec(),
+          y1: y1.to_vec(),
+          y2: y2.to_vec(),
+          where_: where_.map(|w| w.to_vec()),
+          label: label.clone(),
+          color,
+        }
+      }
+    };
+    self.push_series(series)
+  }
+
+  fn bar(&mut self,
+         _x: &[f64],
+         _height: &[f64],
+         _width: &Option<f64>,
+         _bottom: &Option<&[f64]>,
+         _horizontal: bool,
+         _label: &Option<String>,
+         _color: &Option<String>,
+         _edgecolor: &Option<String>,
+         _align: &Option<String>)
+         -> Result<&mut Self> {
+    unsupported("bar")
+  }
+
+  fn hist(&mut self,
+          _data: &[&[f64]],
+          _bins: &Option<Bins>,
+          _weights: &Option<&[&[f64]]>,
+          _density: bool,
+          _cumulative: bool,
+          _histtype: &Option<String>,
+          _label: &Option<String>,
+          _color: &Option<String>)
+          -> Result<&mut Self> {
+    unsupported("hist")
+  }
+
+  fn errorbar(&mut self,
+              _xdata: &[f64],
+              _ydata: &[f64],
+              _xerr: &Option<Deviation>,
+              _yerr: &Option<Deviation>,
+              _capsize: &Option<f64>,
+              _ecolor: &Option<String>,
+              _label: &Option<String>,
+              _color: &Option<String>,
+              _marker: &Option<String>,
+              _linestyle: &Option<String>,
+              _linewidth: &Option<f64>)
+              -> Result<&mut Self> {
+    unsupported("errorbar")
+  }
+
+  fn imshow(&mut self,
+            _data: &[f64],
+            _shape: (usize, usize),
+            _cmap: &Option<String>,
+            _vmin: &Option<f64>,
+            _vmax: &Option<f64>,
+            _interpolation: &Option<String>,
+            _origin: &Option<String>,
+            _extent: &Option<(f64, f64, f64, f64)>)
+            -> Result<&mut Self> {
+    unsupported("imshow")
+  }
+
+  fn contour(&mut self,
+             _x: &[f64],
+             _y: &[f64],
+             _z: &[f64],
+             _levels: &Option<Levels>,
+             _cmap: &Option<String>,
+             _linewidths: &Option<f64>,
+             _clabel: bool)
+             -> Result<&mut Self> {
+    unsupported("contour")
+  }
+
+  fn contourf(&mut self,
+              _x: &[f64],
+              _y: &[f64],
+              _z: &[f64],
+              _levels: &Option<Levels>,
+              _cmap: &Option<String>)
+              -> Result<&mut Self> {
+    unsupported("contourf")
+  }
+
+  /// the layout is always computed when the scene is rendered.
+  fn tight_layout(&mut self) -> Result<&mut Self> {
+    Ok(self)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::{Align, Scene, Series, Ticks, escape, fixed, legend_position, number,
+              parse_color, segments};
+  use axes2d::{Axes2D, Line2D, Scatter};
+  use backend::Backend;
+  use figure::{Figure, Subplots};
+
+  #[test]
+  fn nice_ticks() {
+    let ticks = Ticks::new((-0.3, 2.2), 5);
+    assert_eq!(ticks.values, [0.0, 0.5, 1.0, 1.5, 2.0]);
+    assert_eq!(ticks.format(0.5), "0.5");
+    assert_eq!(ticks.format(1.0), "1.0");
+
+    let ticks = Ticks::new((0.0, 100.0), 5);
+    assert_eq!(ticks.values, [0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+    assert_eq!(ticks.format(40.0), "40");
+
+    let ticks = Ticks::new((0.0, 1.0), 4);
+    assert_eq!(ticks.values, [0.0, 0.25, 0.5, 0.75, 1.0]);
+    assert_eq!(ticks.format(0.5), "0.50");
+  }
+
+  #[test]
+  fn collect_subplots() {
+    let x = [0.0, 1.0, 2.0];
+    let mut scene = Scene::default();
+    Figure::default()
+      .subplots(Subplots::new(1, 2)
+                  .at(0, Axes2D::default().add(Line2D::new("a").data(&x, &x)).xlim(0.0, 4.0))
+                  .at(1,
+                      Axes2D::default()
+                        .add(Scatter::new("b").data(&x, &x).color("r"))
+                        .xlabel("x")))
+      .apply(&mut scene)
+      .unwrap();
+
+    assert_eq!(scene.axes.len(), 2);
+    assert_eq!(scene.axes[0].cell(), (0, 0));
+    assert_eq!(scene.axes[1].cell(), (0, 1));
+    assert_eq!(scene.axes[0].xrange(), (0.0, 4.0));
+    assert_eq!(scene.axes[1].xrange(), (-0.1, 2.1));
+    assert_eq!(scene.axes[1].xlabel.as_deref(), Some("x"));
+    match scene.axes[0].series[0] {
+      Series::Line { ref color, .. } => assert_eq!(color, "#1f77b4"),
+      ref s => panic!("unexpected series: {:?}", s),
+    }
+    match scene.axes[1].series[0] {
+      Series::Scatter { ref color, .. } => assert_eq!(color, "#ff0000"),
+      ref s => panic!("unexpected series: {:?}", s),
+    }
+
+    // the empty limits are expanded, instead of dividing by zero.
+    scene.axes[0].ylim = Some((2.0, 2.0));
+    assert_eq!(scene.axes[0].yrange(), (1.9, 2.1));
+  }
+
+  #[test]
+  fn implicit_axes() {
+    let mut scene = Scene::default();
+    scene.xlabel("x").unwrap();
+    assert_eq!(scene.axes.len(), 1);
+    assert_eq!((scene.axes[0].rows, scene.axes[0].cols, scene.axes[0].n), (1, 1, 1));
+  }
+
+  #[test]
+  fn unsupported_calls() {
+    let mut scene = Scene::default();
+    assert!(scene.set_style("ggplot").is_err());
+    assert!(scene.imshow(&[0.0], (1, 1), &None, &None, &None, &None, &None, &None).is_err());
+  }
+
+  #[test]
+  fn formatting() {
+    assert_eq!(fixed(1.0), "1");
+    assert_eq!(fixed(1.254), "1.25");
+    assert_eq!(fixed(-0.001), "0");
+    assert_eq!(number(-0.0), "0");
+    assert_eq!(number(0.25), "0.25");
+    assert_eq!(number(1e300), "1e300");
+    assert_eq!(number(-1.5e-7), "-1.5e-7");
+    assert_eq!(number(f64::NEG_INFINITY), "-inf");
+    assert_eq!(escape("Bob's <data> & \"more\""),
+               "Bob&#39;s &lt;data&gt; &amp; &quot;more&quot;");
+  }
+
+  #[test]
+  fn step_lengths() {
+    let mut scene = Scene::default();
+    let step = Some("post".to_owned());
+    assert!(scene.fill_between(&[0.0, 1.0, 2.0], &[0.0], &[1.0, 1.0, 1.0], &None, false, &step, &None)
+                 .is_err());
+    scene.fill_between(&[0.0, 1.0], &[0.0, 1.0], &[1.0, 1.0], &None, false, &step, &None).unwrap();
+    match scene.axes[0].series[0] {
+      Series::Area { ref x, .. } => assert_eq!(x, &[0.0, 1.0, 1.0, 1.0]),
+      _ => panic!("an area is expected"),
+    }
+  }
+
+  #[test]
+  fn split_segments() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+    let y = [0.0, 1.0, f64::NAN, 3.0, 4.0];
+    assert_eq!(segments(&x, &y),
+               [vec![(0.0, 0.0), (1.0, 1.0)], vec![(3.0, 3.0), (4.0, 4.0)]]);
+  }
+
+  #[test]
+  fn legend_positions() {
+    assert_eq!(legend_position("best"), (Align::End, Align::End));
+    assert_eq!(legend_position("lower left"), (Align::Start, Align::Start));
+    assert_eq!(legend_position("upper center"), (Align::Center, Align::End));
+    assert_eq!(legend_position("center left"), (Align::Start, Align::Center));
+    assert_eq!(legend_position("center"), (Align::Center, Align::Center));
+  }
+
+  #[test]
+  fn colors() {
+    assert_eq!(parse_color("C1"), Some((0xff, 0x7f, 0x0e)));
+    assert_eq!(parse_color("k"), Some((0, 0, 0)));
+    assert_eq!(parse_color("#abc"), Some((0xaa, 0xbb, 0xcc)));
+    assert_eq!(parse_color("Red"), Some((255, 0, 0)));
+    assert_eq!(parse_color("unknown"), None);
+  }
+}
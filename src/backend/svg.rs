@@ -0,0 +1,574 @@
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use axes2d::Scale;
+use backend::Backend;
+use backend::scene::{Align, Axes, Scene, Series, Ticks, area_runs, dash_pattern, escape, fixed,
+                     legend_position, segments};
+use error::{Error, Result};
+use savefig::{Format, SaveOptions};
+
+/// the font size of texts, in points.
+const FONT_SIZE: f64 = 10.0;
+
+/// Renders figures into SVG images, without Python.
+///
+/// Only `Line2D`, `Scatter` and `FillBetween` with linear axes are supported.
+/// The other plots and the interactive window (`show()`) fail with `Error::Unsupported`.
+#[derive(Debug, Default, Clone)]
+pub struct Svg {
+  scene: Scene,
+}
+
+impl Svg {
+  pub fn new() -> Svg {
+    Svg::default()
+  }
+
+  /// render the current figure into an SVG document.
+  ///
+  /// the resolution, transparency and the metadata `"Title"` in `options` are honored.
+  pub fn render(&self, options: &SaveOptions) -> Result<String> {
+    match options.format {
+      None | Some(Format::Svg) => (),
+      Some(format) => {
+        return Err(Error::Unsupported(format!("cannot render the figure as {}", format.as_str())))
+      }
+    }
+    let dpi = options.dpi.unwrap_or_else(|| self.scene.dpi());
+    let (width, height) = self.scene.figsize();
+    let mut canvas = Canvas::new(width * dpi, height * dpi, dpi / 72.0);
+
+    if let Some(title) = options.metadata.get("Title") {
+      canvas.push(format!("<title>{}</title>", escape(title)));
+    }
+    if !options.transparent {
+      let facecolor = self.scene.facecolor.as_deref().unwrap_or("white");
+      canvas.push(format!("<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+                          escape(facecolor)));
+    }
+    if let Some(ref edgecolor) = self.scene.edgecolor {
+      canvas.push(format!("<rect width=\"100%\" height=\"100%\" fill=\"none\" stroke=\"{}\" \
+                           stroke-width=\"{}\"/>",
+                          escape(edgecolor),
+                          fixed(2.0 * canvas.pt)));
+    }
+
+    let mut top = 0.0;
+    if let Some(ref suptitle) = self.scene.suptitle {
+      top = 2.5 * FONT_SIZE * canvas.pt;
+      let (x, y) = (canvas.width / 2.0, 1.5 * FONT_SIZE * canvas.pt);
+      canvas.text(x, y, suptitle, "middle", 1.2 * FONT_SIZE, None);
+    }
+    for (i, axes) in self.scene.axes.iter().enumerate() {
+      let cell_width = canvas.width / axes.cols as f64;
+      let cell_height = (canvas.height - top) / axes.rows as f64;
+      let (row, col) = axes.cell();
+      let cell = Rect {
+        x: col as f64 * cell_width,
+        y: top + row as f64 * cell_height,
+        width: cell_width,
+        height: cell_height,
+      };
+      canvas.axes(i, axes, &cell)?;
+    }
+    Ok(canvas.finish())
+  }
+
+  /// write the current figure into a file as an SVG document.
+  fn write<P: AsRef<Path>>(&self, filename: P, options: &SaveOptions) -> Result<()> {
+    let svg = self.render(options)?;
+    let mut file = File::create(filename)?;
+    file.write_all(svg.as_bytes())?;
+    Ok(())
+  }
+}
+
+impl Backend for Svg {
+  delegate_to_scene!();
+
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.savefig_with(filename, &SaveOptions::default())
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    if options.format.is_none() {
+      let extension = Path::new(filename).extension().and_then(|e| e.to_str());
+      match extension {
+        None => (),
+        Some(ext) if ext.eq_ignore_ascii_case("svg") => (),
+        Some(ext) => {
+          return Err(Error::Unsupported(format!("cannot save the figure as {}", ext)));
+        }
+      }
+    }
+    self.write(filename, options)?;
+    Ok(self)
+  }
+
+  /// render the current figure as an SVG document (PNG is not supported).
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    self.render(options).map(String::into_bytes)
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    Err(Error::Unsupported("Svg cannot show the figure in a window".to_owned()))
+  }
+}
+
+
+struct Rect {
+  x: f64,
+  y: f64,
+  width: f64,
+  height: f64,
+}
+
+/// Maps the data coordinates into the pixels in the plot area.
+struct Transform {
+  area: Rect,
+  xrange: (f64, f64),
+  yrange: (f64, f64),
+}
+
+impl Transform {
+  fn x(&self, x: f64) -> f64 {
+    self.area.x + (x - self.xrange.0) / (self.xrange.1 - self.xrange.0) * self.area.width
+  }
+
+  fn y(&self, y: f64) -> f64 {
+    self.area.y + self.area.height -
+    (y - self.yrange.0) / (self.yrange.1 - self.yrange.0) * self.area.height
+  }
+
+  fn points(&self, points: &[(f64, f64)]) -> String {
+    let points: Vec<_> = points.iter()
+      .map(|&(x, y)| format!("{},{}", fixed(self.x(x)), fixed(self.y(y))))
+      .collect();
+    points.join(" ")
+  }
+}
+
+struct Canvas {
+  width: f64,
+  height: f64,
+  /// the size of a point (1/72 inch) in pixels.
+  pt: f64,
+  body: String,
+}
+
+impl Canvas {
+  fn new(width: f64, height: f64, pt: f64) -> Canvas {
+    Canvas {
+      width,
+      height,
+      pt,
+      body: String::new(),
+    }
+  }
+
+  fn push<S: AsRef<str>>(&mut self, element: S) {
+    self.body += element.as_ref();
+    self.body.push('\n');
+  }
+
+  fn text(&mut self, x: f64, y: f64, text: &str, anchor: &str, size: f64, rotate: Option<f64>) {
+    let transform = match rotate {
+      Some(angle) => format!(" transform=\"rotate({} {} {})\"", fixed(angle), fixed(x), fixed(y)),
+      None => String::new(),
+    };
+    let element = format!("<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"{}\"{}>{}</text>",
+                          fixed(x),
+                          fixed(y),
+                          fixed(size * self.pt),
+                          anchor,
+                          transform,
+                          escape(text));
+    self.push(element);
+  }
+
+  fn axes(&mut self, id: usize, axes: &Axes, cell: &Rect) -> Result<()> {
+    if axes.xscale != Scale::Linear || axes.yscale != Scale::Linear {
+      return Err(Error::Unsupported("Svg only supports linear scales".to_owned()));
+    }
+    let pt = self.pt;
+    let font = FONT_SIZE * pt;
+    let title_space = if axes.title.is_some() { 2.0 * font } else { 0.0 };
+    let left = 5.5 * font;
+    let bottom = 4.0 * font;
+    let area = Rect {
+      x: cell.x + left,
+      y: cell.y + font + title_space,
+      width: (cell.width - left - 2.0 * font).max(1.0),
+      height: (cell.height - bottom - font - title_space).max(1.0),
+    };
+    let tr = Transform {
+      xrange: axes.xrange(),
+      yrange: axes.yrange(),
+      area,
+    };
+    let xticks = Ticks::new(tr.xrange, max_ticks(tr.area.width, 6.0 * font));
+    let yticks = Ticks::new(tr.yrange, max_ticks(tr.area.height, 3.0 * font));
+    let (x0, y0, x1, y1) = (tr.area.x, tr.area.y, tr.area.x + tr.area.width, tr.area.y + tr.area.height);
+    let in_xrange = |v: f64| (tr.x(v) - x0) > -0.5 && (tr.x(v) - x1) < 0.5;
+    let in_yrange = |v: f64| (tr.y(v) - y0) > -0.5 && (tr.y(v) - y1) < 0.5;
+
+    self.push(format!("<g id=\"axes{}\">", id + 1));
+    self.push(format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>",
+                      fixed(x0),
+                      fixed(y0),
+                      fixed(tr.area.width),
+                      fixed(tr.area.height)));
+    if axes.grid {
+      let style = format!("stroke=\"#b0b0b0\" stroke-width=\"{}\"", fixed(0.8 * pt));
+      for &v in xticks.values.iter().filter(|&&v| in_xrange(v)) {
+        self.push(format!("<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\" {3}/>",
+                          fixed(tr.x(v)),
+                          fixed(y0),
+                          fixed(y1),
+                          style));
+      }
+      for &v in yticks.values.iter().filter(|&&v| in_yrange(v)) {
+        self.push(format!("<line x1=\"{0}\" y1=\"{2}\" x2=\"{1}\" y2=\"{2}\" {3}/>",
+                          fixed(x0),
+                          fixed(x1),
+                          fixed(tr.y(v)),
+                          style));
+      }
+    }
+
+    self.push(format!("<clipPath id=\"clip{}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" \
+                       height=\"{}\"/></clipPath>",
+                      id + 1,
+                      fixed(x0),
+                      fixed(y0),
+                      fixed(tr.area.width),
+                      fixed(tr.area.height)));
+    self.push(format!("<g clip-path=\"url(#clip{})\">", id + 1));
+    for series in &axes.series {
+      self.series(series, &tr)?;
+    }
+    self.push("</g>");
+
+    // spines and ticks.
+    self.push(format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" \
+                       stroke=\"black\" stroke-width=\"{}\"/>",
+                      fixed(x0),
+                      fixed(y0),
+                      fixed(tr.area.width),
+                      fixed(tr.area.height),
+                      fixed(0.8 * pt)));
+    let tick = 3.5 * pt;
+    for &v in xticks.values.iter().filter(|&&v| in_xrange(v)) {
+      let x = tr.x(v);
+      self.push(format!("<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\" stroke=\"black\" \
+                         stroke-width=\"{3}\"/>",
+                        fixed(x),
+                        fixed(y1),
+                        fixed(y1 + tick),
+                        fixed(0.8 * pt)));
+      self.text(x, y1 + tick + 1.2 * font, &xticks.format(v), "middle", FONT_SIZE, None);
+    }
+    for &v in yticks.values.iter().filter(|&&v| in_yrange(v)) {
+      let y = tr.y(v);
+      self.push(format!("<line x1=\"{0}\" y1=\"{2}\" x2=\"{1}\" y2=\"{2}\" stroke=\"black\" \
+                         stroke-width=\"{3}\"/>",
+                        fixed(x0 - tick),
+                        fixed(x0),
+                        fixed(y),
+                        fixed(0.8 * pt)));
+      self.text(x0 - tick - 0.4 * font, y + 0.35 * font, &yticks.format(v), "end", FONT_SIZE, None);
+    }
+
+    if let Some(ref xlabel) = axes.xlabel {
+      self.text((x0 + x1) / 2.0, y1 + tick + 2.8 * font, xlabel, "middle", FONT_SIZE, None);
+    }
+    if let Some(ref ylabel) = axes.ylabel {
+      let (x, y) = (x0 - 4.5 * font, (y0 + y1) / 2.0);
+      self.text(x, y, ylabel, "middle", FONT_SIZE, Some(-90.0));
+    }
+    if let Some(ref title) = axes.title {
+      let (x, anchor) = match axes.title_loc.as_deref() {
+        Some("left") => (x0, "start"),
+        Some("right") => (x1, "end"),
+        _ => ((x0 + x1) / 2.0, "middle"),
+      };
+      self.text(x, y0 - 0.6 * font, title, anchor, 1.2 * FONT_SIZE, None);
+    }
+    if let Some(ref loc) = axes.legend {
+      self.legend(axes, loc, &tr.area);
+    }
+    self.push("</g>");
+    Ok(())
+  }
+
+  fn series(&mut self, series: &Series, tr: &Transform) -> Result<()> {
+    let pt = self.pt;
+    match *series {
+      Series::Line { ref x, ref y, ref color, ref marker, ref linestyle, linewidth, .. } => {
+        let linewidth = linewidth.unwrap_or(1.5) * pt;
+        if let Some(dasharray) = dasharray(linestyle, linewidth)? {
+          for segment in segments(x, y) {
+            self.push(format!("<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" \
+                               stroke-width=\"{}\" stroke-linejoin=\"round\"{}/>",
+                              tr.points(&segment),
+                              escape(color),
+                              fixed(linewidth),
+                              dasharray));
+          }
+        }
+        if let Some(ref marker) = *marker {
+          for &(px, py) in segments(x, y).iter().flatten() {
+            let element = marker_element(marker, tr.x(px), tr.y(py), 6.0 * pt, color);
+            self.push(element);
+          }
+        }
+      }
+      Series::Scatter { ref x, ref y, ref color, ref marker, .. } => {
+        let marker = marker.as_deref().unwrap_or("o");
+        for &(px, py) in segments(x, y).iter().flatten() {
+          let element = marker_element(marker, tr.x(px), tr.y(py), 6.0 * pt, color);
+          self.push(element);
+        }
+      }
+      Series::Area { ref x, ref y1, ref y2, ref where_, ref color, .. } => {
+        for run in area_runs(x, y1, y2, where_) {
+          let mut outline: Vec<_> = run.iter().map(|&(x, y1, _)| (x, y1)).collect();
+          outline.extend(run.iter().rev().map(|&(x, _, y2)| (x, y2)));
+          self.push(format!("<polygon points=\"{}\" fill=\"{}\"/>",
+                            tr.points(&outline),
+                            escape(color)));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn legend(&mut self, axes: &Axes, loc: &str, area: &Rect) {
+    let entries = axes.labeled_series();
+    if entries.is_empty() {
+      return;
+    }
+    let pt = self.pt;
+    let font = FONT_SIZE * pt;
+    let line_height = 1.5 * font;
+    let sample = 2.0 * font;
+    // there is no font metrics, so the width of text is estimated.
+    let text_width = entries.iter()
+      .map(|s| s.label().map(|l| l.chars().count()).unwrap_or(0))
+      .max()
+      .unwrap_or(0) as f64 * 0.6 * font;
+    let width = sample + text_width + 1.5 * font;
+    let height = entries.len() as f64 * line_height + 0.5 * font;
+    let pad = 0.5 * font;
+    let (horizontal, vertical) = legend_position(loc);
+    let x = match horizontal {
+      Align::Start => area.x + pad,
+      Align::Center => area.x + (area.width - width) / 2.0,
+      Align::End => area.x + area.width - width - pad,
+    };
+    let y = match vertical {
+      Align::End => area.y + pad,
+      Align::Center => area.y + (area.height - height) / 2.0,
+      Align::Start => area.y + area.height - height - pad,
+    };
+
+    self.push(format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\" \
+                       fill-opacity=\"0.8\" stroke=\"#cccccc\" stroke-width=\"{}\" rx=\"{}\"/>",
+                      fixed(x),
+                      fixed(y),
+                      fixed(width),
+                      fixed(height),
+                      fixed(0.8 * pt),
+                      fixed(2.0 * pt)));
+    for (i, series) in entries.iter().enumerate() {
+      let cy = y + 0.25 * font + (i as f64 + 0.5) * line_height;
+      let (sx0, sx1) = (x + 0.5 * font, x + 0.5 * font + sample);
+      match **series {
+        Series::Line { ref color, ref marker, ref linestyle, linewidth, .. } => {
+          let linewidth = linewidth.unwrap_or(1.5) * pt;
+          // the style has been validated when the line is drawn.
+          if let Ok(Some(dasharray)) = dasharray(linestyle, linewidth) {
+            self.push(format!("<line x1=\"{0}\" y1=\"{2}\" x2=\"{1}\" y2=\"{2}\" stroke=\"{3}\" \
+                               stroke-width=\"{4}\"{5}/>",
+                              fixed(sx0),
+                              fixed(sx1),
+                              fixed(cy),
+                              escape(color),
+                              fixed(linewidth),
+                              dasharray));
+          }
+          if let Some(ref marker) = *marker {
+            let element = marker_element(marker, (sx0 + sx1) / 2.0, cy, 6.0 * pt, color);
+            self.push(element);
+          }
+        }
+        Series::Scatter { ref color, ref marker, .. } => {
+          let marker = marker.as_deref().unwrap_or("o");
+          let element = marker_element(marker, (sx0 + sx1) / 2.0, cy, 6.0 * pt, color);
+          self.push(element);
+        }
+        Series::Area { ref color, .. } => {
+          self.push(format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                            fixed(sx0),
+                            fixed(cy - 0.35 * font),
+                            fixed(sample),
+                            fixed(0.7 * font),
+                            escape(color)));
+        }
+      }
+      let label = series.label().unwrap_or("");
+      self.text(sx1 + 0.5 * font, cy + 0.35 * font, label, "start", FONT_SIZE, None);
+    }
+  }
+
+  fn finish(self) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(svg, "<?xml version=\"1.0\" encoding=\"utf-8\" standalone=\"no\"?>");
+    let _ = writeln!(svg,
+                     "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+                      viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\">",
+                     fixed(self.width),
+                     fixed(self.height));
+    svg += &self.body;
+    svg += "</svg>\n";
+    svg
+  }
+}
+
+/// the number of ticks, so that the interval is at least `spacing` pixels.
+fn max_ticks(length: f64, spacing: f64) -> usize {
+  ((length / spacing) as usize).clamp(2, 10)
+}
+
+/// convert the line style into the attribute `stroke-dasharray`.
+///
+/// returns `None` if the line is not drawn.
+fn dasharray(linestyle: &Option<String>, linewidth: f64) -> Result<Option<String>> {
+  let pattern = match dash_pattern(linestyle, linewidth)? {
+    Some(pattern) => pattern,
+    None => return Ok(None),
+  };
+  if pattern.is_empty() {
+    return Ok(Some(String::new()));
+  }
+  let pattern: Vec<_> = pattern.iter().map(|&p| fixed(p)).collect();
+  Ok(Some(format!(" stroke-dasharray=\"{}\"", pattern.join(","))))
+}
+
+/// draw a marker (in matplotlib's notation) centered at `(x, y)`.
+fn marker_element(marker: &str, x: f64, y: f64, size: f64, color: &str) -> String {
+  let r = size / 2.0;
+  let color = escape(color);
+  let polygon = |points: &[(f64, f64)]| {
+    let points: Vec<_> = points.iter()
+      .map(|&(dx, dy)| format!("{},{}", fixed(x + dx * r), fixed(y + dy * r)))
+      .collect();
+    format!("<polygon points=\"{}\" fill=\"{}\"/>", points.join(" "), color)
+  };
+  let circle = |r: f64| {
+    format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+            fixed(x),
+            fixed(y),
+            fixed(r),
+            color)
+  };
+  match marker {
+    "." => circle(r / 2.0),
+    "," => {
+      format!("<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+              fixed(x - 0.5),
+              fixed(y - 0.5),
+              color)
+    }
+    "s" => polygon(&[(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]),
+    "D" | "d" => polygon(&[(0.0, -1.2), (1.0, 0.0), (0.0, 1.2), (-1.0, 0.0)]),
+    "^" => polygon(&[(0.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]),
+    "v" => polygon(&[(0.0, 1.0), (1.0, -1.0), (-1.0, -1.0)]),
+    "<" => polygon(&[(-1.0, 0.0), (1.0, -1.0), (1.0, 1.0)]),
+    ">" => polygon(&[(1.0, 0.0), (-1.0, -1.0), (-1.0, 1.0)]),
+    "x" | "+" => {
+      let (a, b) = if marker == "x" { (r, r) } else { (r, 0.0) };
+      format!("<path d=\"M{} {}L{} {}M{} {}L{} {}\" stroke=\"{}\" stroke-width=\"{}\"/>",
+              fixed(x - a),
+              fixed(y - b),
+              fixed(x + a),
+              fixed(y + b),
+              fixed(x - b),
+              fixed(y + a),
+              fixed(x + b),
+              fixed(y - a),
+              color,
+              fixed(size / 6.0))
+    }
+    "None" | "none" | "" | " " => String::new(),
+    _ => circle(r),
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::Svg;
+  use axes2d::{Axes2D, FillBetween, Image, Line2D, Scatter};
+  use backend::Backend;
+  use figure::{Figure, Subplots};
+  use savefig::{Format, SaveOptions};
+
+  #[test]
+  fn render_figure() {
+    let x = [0.0, 1.0, 2.0, 3.0];
+    let y = [0.0, 1.0, 4.0, 9.0];
+    let mut svg = Svg::new();
+    Figure::default()
+      .suptitle("squares")
+      .subplots(Subplots::new(2, 1)
+                  .at(0,
+                      Axes2D::default()
+                        .add(Line2D::new("y = x^2").data(&x, &y).linestyle("--"))
+                        .add(Scatter::new("<points>").data(&x, &y).marker("s"))
+                        .xlabel("x")
+                        .ylabel("y")
+                        .grid(true)
+                        .legend("upper left"))
+                  .at(1,
+                      Axes2D::default()
+                        .add(FillBetween::default().data(&x, &x, &y).label("area"))
+                        .xlim(1.0, 1.0)
+                        .legend("best")))
+      .apply(&mut svg)
+      .unwrap();
+    let doc = svg.render(&SaveOptions::default()).unwrap();
+
+    assert!(doc.starts_with("<?xml"));
+    assert!(doc.contains("width=\"640\" height=\"480\""));
+    assert!(doc.contains(">squares</text>"));
+    assert!(doc.contains("<g id=\"axes1\">"));
+    assert!(doc.contains("<g id=\"axes2\">"));
+    assert!(doc.contains("stroke-dasharray"));
+    assert!(doc.contains("<polygon"));
+    assert!(doc.contains(">&lt;points&gt;</text>"));
+    assert!(doc.contains(">area</text>"));
+    assert!(!doc.contains("NaN"));
+    assert!(doc.trim_end().ends_with("</svg>"));
+  }
+
+  #[test]
+  fn options() {
+    let mut svg = Svg::new();
+    svg.figure(&Some((4.0, 3.0)), &None, &None, &None).unwrap();
+    let doc = svg.render(&SaveOptions::default().dpi(50.0).transparent(true)).unwrap();
+    assert!(doc.contains("width=\"200\" height=\"150\""));
+    assert!(!doc.contains("height=\"100%\""));
+    assert!(svg.render(&SaveOptions::default().format(Format::Png)).is_err());
+    assert!(svg.savefig("figure.png").is_err());
+  }
+
+  #[test]
+  fn unsupported_plot() {
+    let z = [0.0; 4];
+    let result = Axes2D::default().add(Image::default().data(&z, (2, 2))).apply(&mut Svg::new());
+    assert!(result.is_err());
+  }
+}
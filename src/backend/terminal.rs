@@ -0,0 +1,678 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, Write};
+use axes2d::Scale;
+use backend::Backend;
+use backend::scene::{Align, Axes, Scene, Series, Ticks, area_runs, legend_position, parse_color,
+                     segments};
+use error::{Error, Result};
+use savefig::SaveOptions;
+
+/// the quadrant characters, indexed by the bits of upper-left, upper-right, lower-left and
+/// lower-right pixels.
+const QUADRANTS: [char; 16] = [' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄',
+                               '▙', '▟', '█'];
+
+/// RGB components of a color.
+type Color = (u8, u8, u8);
+
+/// Characters used to draw the plots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Charset {
+  /// braille patterns, which have 2x4 pixels per character.
+  Braille,
+  /// quadrant blocks, which have 2x2 pixels per character.
+  Block,
+}
+
+impl Charset {
+  fn pixels(&self) -> (usize, usize) {
+    match *self {
+      Charset::Braille => (2, 4),
+      Charset::Block => (2, 2),
+    }
+  }
+
+  fn glyph(&self, bits: &[bool]) -> char {
+    match *self {
+      Charset::Braille => {
+        // the dots are numbered column by column, except for the bottom row.
+        const DOTS: [u32; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+        let code = bits.iter().zip(&DOTS).filter(|&(&b, _)| b).fold(0, |code, (_, d)| code | d);
+        ::std::char::from_u32(0x2800 + code).unwrap_or(' ')
+      }
+      Charset::Block => {
+        let index = bits.iter().enumerate().filter(|&(_, &b)| b).fold(0, |i, (n, _)| i | 1 << n);
+        QUADRANTS[index]
+      }
+    }
+  }
+}
+
+
+/// Renders figures as text in the terminal, without Python.
+///
+/// Only `Line2D`, `Scatter` and `FillBetween` with linear axes are supported.
+/// `show()` prints the figure to the standard output, and `savefig()` writes it into a text file
+/// (without colors).
+#[derive(Debug, Clone)]
+pub struct Terminal {
+  scene: Scene,
+  size: Option<(usize, usize)>,
+  colors: bool,
+  charset: Charset,
+}
+
+impl Default for Terminal {
+  fn default() -> Terminal {
+    Terminal {
+      scene: Scene::default(),
+      size: None,
+      colors: false,
+      charset: Charset::Braille,
+    }
+  }
+}
+
+impl Terminal {
+  pub fn new() -> Terminal {
+    Terminal::default()
+  }
+
+  /// set the number of columns and rows of the output.
+  ///
+  /// if not set, the size of the terminal connected to the standard output is used.
+  /// when it is not a terminal, the size is taken from `$COLUMNS` and `$LINES` (or 80x24).
+  pub fn size(mut self, columns: usize, rows: usize) -> Self {
+    self.size = Some((columns, rows));
+    self
+  }
+
+  /// set whether the plots are colored with ANSI escape sequences.
+  pub fn colors(mut self, enabled: bool) -> Self {
+    self.colors = enabled;
+    self
+  }
+
+  /// set the characters used to draw the plots.
+  pub fn charset(mut self, charset: Charset) -> Self {
+    self.charset = charset;
+    self
+  }
+
+  /// render the current figure as text.
+  pub fn render(&self) -> Result<String> {
+    self.render_with(self.colors)
+  }
+
+  fn render_with(&self, colors: bool) -> Result<String> {
+    let (width, height) = self.size.unwrap_or_else(terminal_size);
+    let mut screen = Screen::new(width, height);
+    let mut top = 0;
+    if let Some(ref suptitle) = self.scene.suptitle {
+      screen.text(center(0, width, suptitle), 0, suptitle, None);
+      top = 1;
+    }
+    // the size may be too small for the suptitle and subplots.
+    let plot_height = height.saturating_sub(top);
+    for axes in &self.scene.axes {
+      let cols = axes.cols.max(1) as usize;
+      let rows = axes.rows.max(1) as usize;
+      let (row, col) = axes.cell();
+      let cell = Rect {
+        x: col as usize * width / cols,
+        y: top + row as usize * plot_height / rows,
+        width: (width / cols).max(1),
+        height: (plot_height / rows).max(1),
+      };
+      self.axes(&mut screen, axes, &cell)?;
+    }
+    Ok(screen.to_text(colors))
+  }
+
+  fn axes(&self, screen: &mut Screen, axes: &Axes, cell: &Rect) -> Result<()> {
+    if axes.xscale != Scale::Linear || axes.yscale != Scale::Linear {
+      return Err(Error::Unsupported("Terminal only supports linear scales".to_owned()));
+    }
+    let mut top = cell.y;
+    if let Some(ref title) = axes.title {
+      let x = match axes.title_loc.as_deref() {
+        Some("left") => cell.x,
+        Some("right") => (cell.x + cell.width).saturating_sub(title.chars().count()),
+        _ => center(cell.x, cell.width, title),
+      };
+      screen.text(x, top, title, None);
+      top += 1;
+    }
+    // the axis line, tick labels and xlabel are placed below the plot area.
+    let bottom = if axes.xlabel.is_some() { 3 } else { 2 };
+    let area_height = (cell.y + cell.height).saturating_sub(top + bottom);
+    let (xrange, yrange) = (axes.xrange(), axes.yrange());
+    let yticks = Ticks::new(yrange, (area_height / 3).max(2));
+    let ylabels: Vec<_> = yticks.values.iter().map(|&v| yticks.format(v)).collect();
+    let label_width = ylabels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let axis_x = cell.x + if axes.ylabel.is_some() { 2 } else { 0 } + label_width;
+    let area_width = (cell.x + cell.width).saturating_sub(axis_x + 2);
+    if area_width < 2 || area_height < 2 {
+      // too small to draw anything.
+      return Ok(());
+    }
+    let area = Rect {
+      x: axis_x + 1,
+      y: top,
+      width: area_width,
+      height: area_height,
+    };
+    let xticks = Ticks::new(xrange, (area_width / 10).max(2));
+    let column = |v: f64| position(v, xrange, area.width).map(|c| area.x + c);
+    let row = |v: f64| position(v, (yrange.1, yrange.0), area.height).map(|r| area.y + r);
+
+    if axes.grid {
+      for x in xticks.values.iter().filter_map(|&v| column(v)) {
+        for y in area.y..area.y + area.height {
+          screen.put(x, y, '·', None);
+        }
+      }
+      for y in yticks.values.iter().filter_map(|&v| row(v)) {
+        for x in area.x..area.x + area.width {
+          screen.put(x, y, '·', None);
+        }
+      }
+    }
+
+    let (cw, ch) = self.charset.pixels();
+    let mut raster = Raster::new(area.width * cw, area.height * ch, xrange, yrange);
+    for (i, series) in axes.series.iter().enumerate() {
+      raster.draw(series, i + 1);
+    }
+    let colors: Vec<_> = axes.series.iter().map(|s| parse_color(s.color())).collect();
+    for cy in 0..area.height {
+      for cx in 0..area.width {
+        let mut bits = [false; 8];
+        let mut owner = 0;
+        for dy in 0..ch {
+          for dx in 0..cw {
+            let index = raster.get(cx * cw + dx, cy * ch + dy);
+            if index > 0 {
+              bits[dy * cw + dx] = true;
+              owner = owner.max(index);
+            }
+          }
+        }
+        if owner > 0 {
+          let glyph = self.charset.glyph(&bits[..cw * ch]);
+          screen.put(area.x + cx, area.y + cy, glyph, colors[owner - 1]);
+        }
+      }
+    }
+
+    // axes lines and ticks.
+    for y in area.y..area.y + area.height {
+      screen.put(axis_x, y, '│', None);
+    }
+    for x in area.x..area.x + area.width {
+      screen.put(x, area.y + area.height, '─', None);
+    }
+    screen.put(axis_x, area.y + area.height, '└', None);
+    for (&v, label) in yticks.values.iter().zip(&ylabels) {
+      if let Some(y) = row(v) {
+        screen.put(axis_x, y, '┤', None);
+        screen.text(axis_x - label.chars().count(), y, label, None);
+      }
+    }
+    let mut next_free = 0;
+    for &v in &xticks.values {
+      if let Some(x) = column(v) {
+        screen.put(x, area.y + area.height, '┬', None);
+        let label = xticks.format(v);
+        let len = label.chars().count();
+        let start = x.saturating_sub(len / 2).max(area.x.saturating_sub(1));
+        if start >= next_free {
+          screen.text(start, area.y + area.height + 1, &label, None);
+          next_free = start + len + 1;
+        }
+      }
+    }
+    if let Some(ref xlabel) = axes.xlabel {
+      screen.text(center(area.x, area.width, xlabel), area.y + area.height + 2, xlabel, None);
+    }
+    if let Some(ref ylabel) = axes.ylabel {
+      let len = ylabel.chars().count();
+      let start = area.y + area.height.saturating_sub(len) / 2;
+      for (i, c) in ylabel.chars().enumerate() {
+        screen.put(cell.x, start + i, c, None);
+      }
+    }
+    if let Some(ref loc) = axes.legend {
+      legend(screen, axes, loc, &area);
+    }
+    Ok(())
+  }
+}
+
+impl Backend for Terminal {
+  delegate_to_scene!();
+
+  /// write the current figure into a text file.
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.savefig_with(filename, &SaveOptions::default())
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    let text = self.savefig_bytes(options)?;
+    let mut file = File::create(filename)?;
+    file.write_all(&text)?;
+    Ok(self)
+  }
+
+  /// render the current figure as text (other formats are not supported).
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    if let Some(format) = options.format {
+      return Err(Error::Unsupported(format!("cannot render the figure as {}", format.as_str())));
+    }
+    self.render_with(false).map(String::into_bytes)
+  }
+
+  /// print the current figure to the standard output.
+  fn show(&mut self) -> Result<&mut Self> {
+    let text = self.render()?;
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    stdout.write_all(text.as_bytes())?;
+    stdout.flush()?;
+    Ok(self)
+  }
+}
+
+
+struct Rect {
+  x: usize,
+  y: usize,
+  width: usize,
+  height: usize,
+}
+
+/// The characters on the screen, with their colors.
+struct Screen {
+  width: usize,
+  height: usize,
+  cells: Vec<(char, Option<Color>)>,
+}
+
+impl Screen {
+  fn new(width: usize, height: usize) -> Screen {
+    Screen {
+      width,
+      height,
+      cells: vec![(' ', None); width * height],
+    }
+  }
+
+  fn put(&mut self, x: usize, y: usize, c: char, color: Option<Color>) {
+    if x < self.width && y < self.height {
+      self.cells[y * self.width + x] = (c, color);
+    }
+  }
+
+  fn text(&mut self, x: usize, y: usize, text: &str, color: Option<Color>) {
+    for (i, c) in text.chars().enumerate() {
+      self.put(x + i, y, c, color);
+    }
+  }
+
+  fn to_text(&self, colors: bool) -> String {
+    let mut text = String::new();
+    for row in self.cells.chunks(self.width.max(1)) {
+      let mut current = None;
+      let mut line = String::new();
+      for &(c, color) in row {
+        let color = if colors { color } else { None };
+        if color != current {
+          match color {
+            Some((r, g, b)) => line += &format!("\x1b[38;2;{};{};{}m", r, g, b),
+            None => line += "\x1b[0m",
+          }
+          current = color;
+        }
+        line.push(c);
+      }
+      if current.is_some() {
+        line += "\x1b[0m";
+      }
+      text += line.trim_end();
+      text.push('\n');
+    }
+    text
+  }
+}
+
+/// The pixels of the plot area, each of which holds the (1-based) index of series drawn on it.
+struct Raster {
+  width: usize,
+  height: usize,
+  xrange: (f64, f64),
+  yrange: (f64, f64),
+  pixels: Vec<usize>,
+}
+
+impl Raster {
+  fn new(width: usize, height: usize, xrange: (f64, f64), yrange: (f64, f64)) -> Raster {
+    Raster {
+      width,
+      height,
+      xrange,
+      yrange,
+      pixels: vec![0; width * height],
+    }
+  }
+
+  fn get(&self, x: usize, y: usize) -> usize {
+    self.pixels[y * self.width + x]
+  }
+
+  fn set(&mut self, x: f64, y: f64, index: usize) {
+    let (x, y) = (x.round(), y.round());
+    if x >= 0.0 && y >= 0.0 && (x as usize) < self.width && (y as usize) < self.height {
+      self.pixels[y as usize * self.width + x as usize] = index;
+    }
+  }
+
+  /// convert data coordinates into (fractional) pixel coordinates.
+  fn project(&self, (x, y): (f64, f64)) -> (f64, f64) {
+    let px = (x - self.xrange.0) / (self.xrange.1 - self.xrange.0) * (self.width - 1) as f64;
+    let py = (self.yrange.1 - y) / (self.yrange.1 - self.yrange.0) * (self.height - 1) as f64;
+    (px, py)
+  }
+
+  fn draw(&mut self, series: &Series, index: usize) {
+    match *series {
+      Series::Line { ref x, ref y, ref marker, ref linestyle, .. } => {
+        let hidden = matches!(linestyle.as_deref(), Some("") | Some(" ") | Some("None") | Some("none"));
+        for segment in segments(x, y) {
+          let points: Vec<_> = segment.iter().map(|&p| self.project(p)).collect();
+          if !hidden {
+            for pair in points.windows(2) {
+              self.line(pair[0], pair[1], index);
+            }
+          }
+          if hidden || marker.is_some() || points.len() == 1 {
+            for &(px, py) in &points {
+              self.set(px, py, index);
+            }
+          }
+        }
+      }
+      Series::Scatter { ref x, ref y, .. } => {
+        for &p in segments(x, y).iter().flatten() {
+          let (px, py) = self.project(p);
+          self.set(px, py, index);
+        }
+      }
+      Series::Area { ref x, ref y1, ref y2, ref where_, .. } => {
+        for run in area_runs(x, y1, y2, where_) {
+          let run: Vec<_> = run.iter()
+            .map(|&(x, y1, y2)| {
+              let (px, py1) = self.project((x, y1));
+              (px, py1, self.project((x, y2)).1)
+            })
+            .collect();
+          if run.len() == 1 {
+            self.fill_column(run[0].0, run[0].1, run[0].2, index);
+          }
+          for pair in run.windows(2) {
+            let ((xa, a1, a2), (xb, b1, b2)) = (pair[0], pair[1]);
+            let (lo, hi) = (xa.min(xb).ceil().max(0.0), xa.max(xb).floor().min(self.width as f64));
+            let mut column = lo;
+            while column <= hi {
+              let t = if xb != xa { (column - xa) / (xb - xa) } else { 0.0 };
+              self.fill_column(column, a1 + (b1 - a1) * t, a2 + (b2 - a2) * t, index);
+              column += 1.0;
+            }
+          }
+        }
+      }
+    }
+  }
+
+  fn fill_column(&mut self, x: f64, y1: f64, y2: f64, index: usize) {
+    let lo = y1.min(y2).round().max(0.0);
+    let hi = y1.max(y2).round().min(self.height as f64);
+    let mut y = lo;
+    while y <= hi {
+      self.set(x, y, index);
+      y += 1.0;
+    }
+  }
+
+  /// draw a line between two pixels, clipped to the raster.
+  fn line(&mut self, p0: (f64, f64), p1: (f64, f64), index: usize) {
+    let bounds = (self.width as f64 - 0.5, self.height as f64 - 0.5);
+    let ((x0, y0), (x1, y1)) = match clip(p0, p1, bounds) {
+      Some(line) => line,
+      None => return,
+    };
+    let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0) as usize;
+    for i in 0..steps + 1 {
+      let t = i as f64 / steps as f64;
+      self.set(x0 + (x1 - x0) * t, y0 + (y1 - y0) * t, index);
+    }
+  }
+}
+
+/// clip a line segment into the rectangle `[-0.5, bounds]` (Liang-Barsky algorithm).
+fn clip(p0: (f64, f64), p1: (f64, f64), bounds: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+  let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+  let (mut t0, mut t1) = (0.0f64, 1.0f64);
+  let edges = [(-dx, p0.0 + 0.5), (dx, bounds.0 - p0.0), (-dy, p0.1 + 0.5), (dy, bounds.1 - p0.1)];
+  for &(p, q) in &edges {
+    if p == 0.0 {
+      if q < 0.0 {
+        return None;
+      }
+    } else {
+      let r = q / p;
+      if p < 0.0 {
+        t0 = t0.max(r);
+      } else {
+        t1 = t1.min(r);
+      }
+    }
+  }
+  if t0 > t1 {
+    return None;
+  }
+  Some(((p0.0 + t0 * dx, p0.1 + t0 * dy), (p0.0 + t1 * dx, p0.1 + t1 * dy)))
+}
+
+fn legend(screen: &mut Screen, axes: &Axes, loc: &str, area: &Rect) {
+  let entries: Vec<_> = axes.labeled_series()
+    .into_iter()
+    .map(|s| {
+      let symbol = match *s {
+        Series::Scatter { .. } => '•',
+        Series::Area { .. } => '█',
+        Series::Line { .. } => '─',
+      };
+      (symbol, s.label().unwrap_or(""), parse_color(s.color()))
+    })
+    .collect();
+  if entries.is_empty() {
+    return;
+  }
+  let width = entries.iter().map(|e| e.1.chars().count()).max().unwrap_or(0) + 4;
+  let height = entries.len();
+  let (horizontal, vertical) = legend_position(loc);
+  let x = match horizontal {
+    Align::Start => area.x + 1,
+    Align::Center => area.x + area.width.saturating_sub(width) / 2,
+    Align::End => (area.x + area.width).saturating_sub(width),
+  };
+  let y = match vertical {
+    Align::End => area.y,
+    Align::Center => area.y + area.height.saturating_sub(height) / 2,
+    Align::Start => (area.y + area.height).saturating_sub(height),
+  };
+  for (i, &(symbol, label, color)) in entries.iter().enumerate() {
+    screen.text(x, y + i, &" ".repeat(width), None);
+    screen.put(x + 1, y + i, symbol, color);
+    screen.text(x + 3, y + i, label, None);
+  }
+}
+
+/// the index of the character at `value` in `range`, mapped onto `length` characters.
+fn position(value: f64, range: (f64, f64), length: usize) -> Option<usize> {
+  let t = (value - range.0) / (range.1 - range.0);
+  let pos = (t * (length - 1) as f64).round();
+  if pos >= 0.0 && pos < length as f64 {
+    Some(pos as usize)
+  } else {
+    None
+  }
+}
+
+/// the column where `text` starts, to be centered in the `width` columns from `x`.
+fn center(x: usize, width: usize, text: &str) -> usize {
+  x + width.saturating_sub(text.chars().count()) / 2
+}
+
+/// the size of the terminal, from the standard output, `$COLUMNS` and `$LINES`, or 80x24.
+fn terminal_size() -> (usize, usize) {
+  let (columns, rows) = tty_size().unwrap_or_else(|| {
+    let var = |name: &str, default: usize| {
+      env::var(name).ok().and_then(|v| v.trim().parse().ok()).filter(|&n| n > 0).unwrap_or(default)
+    };
+    (var("COLUMNS", 80), var("LINES", 24))
+  });
+  // leave a row for the prompt.
+  (columns, rows.saturating_sub(1).max(1))
+}
+
+/// query the size of the terminal connected to the standard output.
+#[cfg(unix)]
+fn tty_size() -> Option<(usize, usize)> {
+  use libc::{STDOUT_FILENO, TIOCGWINSZ, ioctl, winsize};
+
+  let mut size = winsize {
+    ws_row: 0,
+    ws_col: 0,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+  };
+  // it fails if the standard output is not a terminal.
+  let result = unsafe { ioctl(STDOUT_FILENO, TIOCGWINSZ, &mut size) };
+  if result == 0 && size.ws_col > 0 && size.ws_row > 0 {
+    Some((size.ws_col as usize, size.ws_row as usize))
+  } else {
+    None
+  }
+}
+
+#[cfg(not(unix))]
+fn tty_size() -> Option<(usize, usize)> {
+  None
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::{Charset, Terminal, clip};
+  use axes2d::{Axes2D, FillBetween, Line2D, Scatter};
+  use figure::{Figure, Subplots};
+
+  #[test]
+  fn render_line() {
+    let x = [0.0, 1.0];
+    let mut term = Terminal::new().size(40, 12);
+    Axes2D::default()
+      .add(Line2D::new("diagonal").data(&x, &x))
+      .xlabel("time")
+      .ylabel("y")
+      .legend("upper left")
+      .xlim(0.0, 1.0)
+      .ylim(0.0, 1.0)
+      .apply(&mut term)
+      .unwrap();
+    let text = term.render().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+
+    assert_eq!(lines.len(), 12);
+    assert!(lines.iter().all(|l| l.chars().count() <= 40));
+    assert!(text.contains("─ diagonal"));
+    assert!(lines[11].contains("time"));
+    assert!(lines[9].contains('└'));
+    assert!(lines[10].trim_start().starts_with("0.0"));
+    // the line goes from the lower left to the upper right.
+    let braille = |l: &str| l.chars().position(|c| ('\u{2801}'..='\u{28ff}').contains(&c));
+    assert!(braille(lines[8]).unwrap() < braille(lines[1]).unwrap());
+  }
+
+  #[test]
+  fn tiny_size() {
+    let x = [0.0, 1.0];
+    for &(width, height) in &[(20, 0), (20, 1), (0, 2), (1, 1)] {
+      let mut term = Terminal::new().size(width, height);
+      Figure::default()
+        .suptitle("title")
+        .subplots(Subplots::new(2, 2).at(3, Axes2D::default().add(Line2D::new("").data(&x, &x))))
+        .apply(&mut term)
+        .unwrap();
+      term.render().unwrap();
+    }
+  }
+
+  #[test]
+  fn empty_limits() {
+    let mut term = Terminal::new().size(30, 10);
+    Axes2D::default()
+      .add(Line2D::new("flat").data(&[0.0, 2.0], &[1.0, 1.0]))
+      .xlim(1.0, 1.0)
+      .ylim(1.0, 1.0)
+      .apply(&mut term)
+      .unwrap();
+    let text = term.render().unwrap();
+    assert!(text.chars().any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)));
+  }
+
+  #[test]
+  fn render_subplots() {
+    let x = [0.0, 1.0, 2.0];
+    let mut term = Terminal::new().size(60, 20).charset(Charset::Block);
+    Figure::default()
+      .suptitle("overview")
+      .subplots(Subplots::new(1, 2)
+                  .at(0, Axes2D::default().add(Scatter::new("points").data(&x, &x)).title("left"))
+                  .at(1,
+                      Axes2D::default()
+                        .add(FillBetween::default().data(&x, &[0.0; 3], &x).label("area"))
+                        .legend("upper left")))
+      .apply(&mut term)
+      .unwrap();
+    let text = term.render().unwrap();
+    let lines: Vec<_> = text.lines().collect();
+
+    assert!(lines[0].trim() == "overview");
+    assert!(lines[1].contains("left"));
+    assert!(text.contains('█'));
+    assert!(text.contains("█ area"));
+    // two plot areas side by side.
+    assert_eq!(lines[5].matches('│').count() + lines[5].matches('┤').count(), 2);
+    assert!(!text.contains('\x1b'));
+  }
+
+  #[test]
+  fn ansi_colors() {
+    let x = [0.0, 1.0];
+    let mut term = Terminal::new().size(30, 10).colors(true);
+    Axes2D::default().add(Line2D::new("red").data(&x, &x).color("r")).apply(&mut term).unwrap();
+    let text = term.render().unwrap();
+    assert!(text.contains("\x1b[38;2;255;0;0m"));
+    assert!(text.contains("\x1b[0m"));
+  }
+
+  #[test]
+  fn clip_lines() {
+    assert_eq!(clip((-10.0, 0.0), (10.0, 0.0), (4.5, 4.5)),
+               Some(((-0.5, 0.0), (4.5, 0.0))));
+    assert_eq!(clip((-10.0, -10.0), (-5.0, -1.0), (4.5, 4.5)), None);
+  }
+}
@@ -0,0 +1,385 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use axes2d::Scale;
+use backend::Backend;
+use backend::json::Json;
+use backend::scene::{Align, Axes, Scene, Series, area_runs, dash_pattern, legend_position,
+                     segments};
+use error::{Error, Result};
+use savefig::SaveOptions;
+
+const SCHEMA: &str = "https://vega.github.io/schema/vega-lite/v5.json";
+
+/// Exports figures as Vega-Lite specifications (JSON), with the data inlined.
+///
+/// Each axes becomes a layered view, and subplots are arranged by `vconcat` and `hconcat`.
+/// Only `Line2D`, `Scatter` and `FillBetween` are supported.
+#[derive(Debug, Default, Clone)]
+pub struct VegaLite {
+  scene: Scene,
+}
+
+impl VegaLite {
+  pub fn new() -> VegaLite {
+    VegaLite::default()
+  }
+
+  /// generate the specification of the current figure.
+  pub fn spec(&self) -> Result<Json> {
+    let (width, height) = self.scene.figsize();
+    let dpi = self.scene.dpi();
+    let mut spec = Json::object(vec![("$schema", Json::from(SCHEMA))]);
+    if let Some(ref suptitle) = self.scene.suptitle {
+      spec.insert("title", Json::from(suptitle.as_str()));
+    }
+    if let Some(ref facecolor) = self.scene.facecolor {
+      spec.insert("background", Json::from(facecolor.as_str()));
+    }
+
+    match self.scene.axes.len() {
+      0 => spec.insert("layer", Json::Array(Vec::new())),
+      1 => {
+        let axes = &self.scene.axes[0];
+        // the room for the axes and labels is excluded from the size of view.
+        let view = view(axes, width * dpi - 80.0, height * dpi - 70.0)?;
+        if let Json::Object(members) = view {
+          for (key, value) in members {
+            spec.insert(key, value);
+          }
+        }
+      }
+      _ => {
+        let mut rows: Vec<(u32, Vec<(u32, Json)>)> = Vec::new();
+        for axes in &self.scene.axes {
+          let (row, col) = axes.cell();
+          let view = view(axes,
+                          width * dpi / axes.cols as f64 - 80.0,
+                          height * dpi / axes.rows as f64 - 70.0)?;
+          match rows.iter_mut().find(|r| r.0 == row) {
+            Some(r) => r.1.push((col, view)),
+            None => rows.push((row, vec![(col, view)])),
+          }
+        }
+        rows.sort_by_key(|r| r.0);
+        let rows = rows.into_iter()
+          .map(|(_, mut views)| {
+            views.sort_by_key(|v| v.0);
+            Json::object(vec![("hconcat", Json::Array(views.into_iter().map(|v| v.1).collect()))])
+          })
+          .collect();
+        spec.insert("vconcat", Json::Array(rows));
+      }
+    }
+    Ok(spec)
+  }
+}
+
+impl Backend for VegaLite {
+  delegate_to_scene!();
+
+  /// write the specification of the current figure into a JSON file.
+  fn savefig(&mut self, filename: &str) -> Result<&mut Self> {
+    self.savefig_with(filename, &SaveOptions::default())
+  }
+
+  fn savefig_with(&mut self, filename: &str, options: &SaveOptions) -> Result<&mut Self> {
+    let extension = Path::new(filename).extension().and_then(|e| e.to_str());
+    match extension {
+      None => (),
+      Some(ext) if ext.eq_ignore_ascii_case("json") => (),
+      Some(ext) => return Err(Error::Unsupported(format!("cannot save the figure as {}", ext))),
+    }
+    let spec = self.savefig_bytes(options)?;
+    let mut file = File::create(filename)?;
+    file.write_all(&spec)?;
+    Ok(self)
+  }
+
+  /// generate the specification of the current figure as JSON.
+  ///
+  /// the image formats are not supported.
+  fn savefig_bytes(&mut self, options: &SaveOptions) -> Result<Vec<u8>> {
+    if let Some(format) = options.format {
+      return Err(Error::Unsupported(format!("cannot render the figure as {}", format.as_str())));
+    }
+    Ok(self.spec()?.pretty().into_bytes())
+  }
+
+  fn show(&mut self) -> Result<&mut Self> {
+    Err(Error::Unsupported("VegaLite cannot show the figure".to_owned()))
+  }
+}
+
+
+/// the layered view of an axes.
+fn view(axes: &Axes, width: f64, height: f64) -> Result<Json> {
+  let mut view = Json::object(vec![("width", Json::from(width.max(50.0).round())),
+                                   ("height", Json::from(height.max(50.0).round()))]);
+  if let Some(ref title) = axes.title {
+    let anchor = match axes.title_loc.as_deref() {
+      Some("left") => "start",
+      Some("right") => "end",
+      _ => "middle",
+    };
+    view.insert("title",
+                Json::object(vec![("text", Json::from(title.as_str())),
+                                  ("anchor", Json::from(anchor))]));
+  }
+
+  let xaxis = position(&axes.xlabel, axes.xscale, axes.xlim, axes.xrange(), axes.grid)?;
+  let yaxis = position(&axes.ylabel, axes.yscale, axes.ylim, axes.yrange(), axes.grid)?;
+  // the labeled series share a color scale, from which the legend is created.
+  let (mut labels, mut colors) = (Vec::new(), Vec::new());
+  if axes.legend.is_some() {
+    for series in axes.labeled_series() {
+      let label = series.label().unwrap_or("");
+      if !labels.contains(&Json::from(label)) {
+        labels.push(Json::from(label));
+        colors.push(Json::from(series.color()));
+      }
+    }
+  }
+  let legend = match axes.legend.as_ref().map(|loc| legend_position(loc)) {
+    Some(position) => {
+      let orient = match position {
+        (Align::Start, Align::End) => "top-left",
+        (Align::Start, Align::Start) => "bottom-left",
+        (Align::End, Align::Start) => "bottom-right",
+        (Align::Start, Align::Center) => "left",
+        (Align::Center, Align::End) => "top",
+        (Align::Center, Align::Start) => "bottom",
+        (Align::End, Align::Center) | (Align::Center, Align::Center) => "right",
+        (Align::End, Align::End) => "top-right",
+      };
+      Json::object(vec![("orient", Json::from(orient)), ("title", Json::Null)])
+    }
+    None => Json::Null,
+  };
+
+  let mut layers = Vec::new();
+  for series in &axes.series {
+    let (data, mut mark, mut encoding) = match *series {
+      Series::Line { ref x, ref y, ref marker, ref linestyle, linewidth, .. } => {
+        let dash = dash_pattern(linestyle, linewidth.unwrap_or(1.5))?;
+        let mut mark = match (dash, marker.as_deref()) {
+          (None, None) => continue,
+          (None, Some(marker)) => point_mark(marker),
+          (Some(dash), marker) => {
+            let width = Json::from(linewidth.unwrap_or(1.5));
+            let mut mark = Json::object(vec![("type", Json::from("line")), ("strokeWidth", width)]);
+            if !dash.is_empty() {
+              mark.insert("strokeDash", Json::numbers(&dash));
+            }
+            if let Some(marker) = marker {
+              mark.insert("point", point_mark(marker));
+            }
+            mark
+          }
+        };
+        mark.insert("clip", Json::from(true));
+        let data: Vec<_> = segments(x, y)
+          .iter()
+          .enumerate()
+          .flat_map(|(i, s)| {
+            s.iter()
+              .map(move |&(x, y)| {
+                Json::object(vec![("x", Json::from(x)),
+                                  ("y", Json::from(y)),
+                                  ("segment", Json::from(i as f64))])
+              })
+          })
+          .collect();
+        let encoding = vec![("x", channel("x", &xaxis)),
+                            ("y", channel("y", &yaxis)),
+                            ("detail", field("segment"))];
+        (data, mark, encoding)
+      }
+      Series::Scatter { ref x, ref y, ref marker, .. } => {
+        let mut mark = point_mark(marker.as_deref().unwrap_or("o"));
+        mark.insert("clip", Json::from(true));
+        let data = segments(x, y)
+          .iter()
+          .flat_map(|s| s.iter())
+          .map(|&(x, y)| Json::object(vec![("x", Json::from(x)), ("y", Json::from(y))]))
+          .collect();
+        (data, mark, vec![("x", channel("x", &xaxis)), ("y", channel("y", &yaxis))])
+      }
+      Series::Area { ref x, ref y1, ref y2, ref where_, .. } => {
+        let mark = Json::object(vec![("type", Json::from("area")), ("clip", Json::from(true))]);
+        let data = area_runs(x, y1, y2, where_)
+          .iter()
+          .enumerate()
+          .flat_map(|(i, run)| {
+            run.iter()
+              .map(move |&(x, y1, y2)| {
+                Json::object(vec![("x", Json::from(x)),
+                                  ("y1", Json::from(y1)),
+                                  ("y2", Json::from(y2)),
+                                  ("run", Json::from(i as f64))])
+              })
+          })
+          .collect();
+        let encoding = vec![("x", channel("x", &xaxis)),
+                            ("y", channel("y1", &yaxis)),
+                            ("y2", field("y2")),
+                            ("detail", field("run"))];
+        (data, mark, encoding)
+      }
+    };
+    match series.label() {
+      Some(label) if !labels.is_empty() => {
+        let scale = Json::object(vec![("domain", Json::Array(labels.clone())),
+                                      ("range", Json::Array(colors.clone()))]);
+        encoding.push(("color",
+                       Json::object(vec![("datum", Json::from(label)),
+                                         ("scale", scale),
+                                         ("legend", legend.clone())])));
+      }
+      _ => mark.insert("color", Json::from(series.color())),
+    }
+    layers.push(Json::object(vec![("data",
+                                   Json::object(vec![("values", Json::Array(data))])),
+                                  ("mark", mark),
+                                  ("encoding", Json::object(encoding))]));
+  }
+  if layers.is_empty() {
+    // an empty layer, to draw the axes.
+    let encoding = Json::object(vec![("x", channel("x", &xaxis)), ("y", channel("y", &yaxis))]);
+    layers.push(Json::object(vec![("data", Json::object(vec![("values", Json::Array(Vec::new()))])),
+                                  ("mark", Json::from("point")),
+                                  ("encoding", encoding)]));
+  }
+  view.insert("layer", Json::Array(layers));
+  Ok(view)
+}
+
+/// the encoding of a position channel, which refers to `field`.
+fn channel(field: &str, axis: &[(String, Json)]) -> Json {
+  let mut members = vec![("field".to_owned(), Json::from(field))];
+  members.extend(axis.iter().cloned());
+  Json::Object(members)
+}
+
+fn field(name: &str) -> Json {
+  Json::object(vec![("field", Json::from(name))])
+}
+
+/// the properties of x or y channel: the axis title, the scale and the grid.
+fn position(label: &Option<String>,
+            scale: Scale,
+            limits: Option<(f64, f64)>,
+            range: (f64, f64),
+            grid: bool)
+            -> Result<Vec<(String, Json)>> {
+  let (mut scale, domain) = match scale {
+    // same as matplotlib, the linear axes have margins around the data.
+    Scale::Linear => (Json::object(vec![("type", Json::from("linear"))]), Some(range)),
+    Scale::Log { base } => {
+      (Json::object(vec![("type", Json::from("log")), ("base", Json::from(base))]), limits)
+    }
+    Scale::SymLog { linthresh } => {
+      (Json::object(vec![("type", Json::from("symlog")), ("constant", Json::from(linthresh))]),
+       limits)
+    }
+    Scale::Logit => {
+      return Err(Error::Unsupported("Vega-Lite does not support the logit scale".to_owned()))
+    }
+  };
+  if let Some((lb, ub)) = domain {
+    scale.insert("domain", Json::numbers(&[lb, ub]));
+  }
+  scale.insert("nice", Json::from(false));
+  scale.insert("zero", Json::from(false));
+  let title = match *label {
+    Some(ref label) => Json::from(label.as_str()),
+    None => Json::Null,
+  };
+  Ok(vec![("type".to_owned(), Json::from("quantitative")),
+          ("title".to_owned(), title),
+          ("scale".to_owned(), scale),
+          ("axis".to_owned(), Json::object(vec![("grid", Json::from(grid))]))])
+}
+
+/// the mark of points, drawn with the marker (in matplotlib's notation).
+fn point_mark(marker: &str) -> Json {
+  let (shape, size) = match marker {
+    "." => ("circle", 9.0),
+    "s" => ("square", 36.0),
+    "^" => ("triangle-up", 36.0),
+    "v" => ("triangle-down", 36.0),
+    "<" => ("triangle-left", 36.0),
+    ">" => ("triangle-right", 36.0),
+    "D" | "d" => ("diamond", 36.0),
+    "+" | "x" => ("cross", 36.0),
+    _ => ("circle", 36.0),
+  };
+  let mut mark = Json::object(vec![("type", Json::from("point")),
+                                   ("filled", Json::from(true)),
+                                   ("shape", Json::from(shape)),
+                                   ("size", Json::from(size))]);
+  if marker == "x" {
+    mark.insert("angle", Json::from(45.0));
+  }
+  mark
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::VegaLite;
+  use axes2d::{Axes2D, FillBetween, Line2D, Scale, Scatter};
+  use backend::Backend;
+  use figure::{Figure, Subplots};
+
+  #[test]
+  fn single_view() {
+    let x = [0.0, 1.0, 2.0];
+    let y = [0.0, f64::NAN, 4.0];
+    let mut vega = VegaLite::new();
+    Axes2D::default()
+      .add(Line2D::new("data").data(&x, &y).color("r").linestyle("--"))
+      .add(Scatter::new("").data(&x, &x))
+      .xlabel("time")
+      .legend("upper left")
+      .xlim(0.0, 2.0)
+      .apply(&mut vega)
+      .unwrap();
+    let spec = vega.spec().unwrap().to_string();
+
+    assert!(spec.starts_with(r##"{"$schema":"https://vega.github.io/schema/vega-lite/v5.json","width":560,"height":410,"layer":["##));
+    assert!(spec.contains(r##"{"x":0,"y":0,"segment":0},{"x":2,"y":4,"segment":1}"##));
+    assert!(spec.contains(r##""mark":{"type":"line","strokeWidth":1.5,"strokeDash":[5.550000000000001,2.4000000000000004],"clip":true}"##));
+    assert!(spec.contains(r##""field":"x","type":"quantitative","title":"time","scale":{"type":"linear","domain":[0,2],"nice":false,"zero":false}"##));
+    assert!(spec.contains(r##""color":{"datum":"data","scale":{"domain":["data"],"range":["#ff0000"]},"legend":{"orient":"top-left","title":null}}"##));
+    // the unlabeled series is not in the legend.
+    assert!(spec.contains(r##""type":"point","filled":true,"shape":"circle","size":36,"clip":true,"color":"#ff7f0e"}"##));
+  }
+
+  #[test]
+  fn concatenated_views() {
+    let x = [0.0, 1.0];
+    let mut vega = VegaLite::new();
+    Figure::default()
+      .suptitle("overview")
+      .subplots(Subplots::new(2, 2)
+                  .at(3, Axes2D::default().add(FillBetween::default().data(&x, &x, &[2.0, 2.0])))
+                  .at(0, Axes2D::default().title("first").yscale(Scale::Log { base: 10.0 })))
+      .apply(&mut vega)
+      .unwrap();
+    let spec = vega.spec().unwrap().to_string();
+
+    assert!(spec.contains(r##""title":"overview","vconcat":[{"hconcat":[{"width":240,"height":170,"title":{"text":"first","anchor":"middle"}"##));
+    assert!(spec.contains(r##""scale":{"type":"log","base":10,"nice":false,"zero":false}"##));
+    assert!(spec.contains(r##"]},{"hconcat":[{"width":240,"height":170,"layer":[{"data":{"values":[{"x":0,"y1":0,"y2":2,"run":0}"##));
+    assert!(spec.contains(r##""y2":{"field":"y2"},"detail":{"field":"run"}"##));
+  }
+
+  #[test]
+  fn unsupported() {
+    let mut vega = VegaLite::new();
+    vega.xscale(&Scale::Logit).unwrap();
+    assert!(vega.spec().is_err());
+    assert!(VegaLite::new().savefig("figure.png").is_err());
+  }
+}
@@ -0,0 +1,63 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+
+/// Errors which occur while drawing figures.
+#[derive(Debug)]
+pub enum Error {
+  /// failed to communicate with the backend.
+  Io(io::Error),
+  /// an exception was raised in Python.
+  Python {
+    /// the name of exception type (e.g. `"ValueError"`).
+    exception: String,
+    /// the message of exception.
+    message: String,
+    /// the formatted traceback, if available.
+    traceback: Option<String>,
+  },
+  /// the plot data or its configuration is invalid.
+  InvalidInput(String),
+  /// the backend cannot be used (e.g. the interpreter or matplotlib is not installed).
+  NotAvailable(String),
+  /// the operation is not supported by the backend.
+  Unsupported(String),
+  /// an external program used by the backend failed to draw the figure.
+  Backend {
+    /// the name of backend (e.g. `"gnuplot"`).
+    name: String,
+    /// the error message reported by the program.
+    message: String,
+  },
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl From<io::Error> for Error {
+  fn from(err: io::Error) -> Error {
+    Error::Io(err)
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Error::Io(ref err) => write!(f, "I/O error: {}", err),
+      Error::Python { ref exception, ref message, .. } => write!(f, "{}: {}", exception, message),
+      Error::InvalidInput(ref message) => write!(f, "invalid input: {}", message),
+      Error::NotAvailable(ref message) => write!(f, "backend not available: {}", message),
+      Error::Unsupported(ref message) => write!(f, "unsupported operation: {}", message),
+      Error::Backend { ref name, ref message } => write!(f, "{} error: {}", name, message),
+    }
+  }
+}
+
+impl error::Error for Error {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match *self {
+      Error::Io(ref err) => Some(err),
+      _ => None,
+    }
+  }
+}
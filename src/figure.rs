@@ -1,11 +1,16 @@
-use std::io;
 use axes2d::Axes2D;
 use backend::Backend;
+use error::Result;
 
 /// Represents an instance of `matplotlib.figure.Figure`.
 #[derive(Debug, Default)]
 pub struct Figure<'a> {
   subplots: Option<Subplots<'a>>,
+  suptitle: Option<String>,
+  figsize: Option<(f64, f64)>,
+  dpi: Option<f64>,
+  facecolor: Option<String>,
+  edgecolor: Option<String>,
 }
 
 impl<'a> Figure<'a> {
@@ -14,8 +19,41 @@ impl<'a> Figure<'a> {
     self
   }
 
-  pub fn apply<B: Backend>(&self, mpl: &mut B) -> io::Result<()> {
-    mpl.figure()?;
+  /// set the width and height of the figure, in inches.
+  pub fn figsize(mut self, width: f64, height: f64) -> Self {
+    self.figsize = Some((width, height));
+    self
+  }
+
+  /// set the resolution of the figure, in dots per inch.
+  pub fn dpi(mut self, dpi: f64) -> Self {
+    self.dpi = Some(dpi);
+    self
+  }
+
+  /// set the background color of the figure.
+  pub fn facecolor(mut self, color: &str) -> Self {
+    self.facecolor = Some(color.to_owned());
+    self
+  }
+
+  /// set the border color of the figure.
+  pub fn edgecolor(mut self, color: &str) -> Self {
+    self.edgecolor = Some(color.to_owned());
+    self
+  }
+
+  /// set the title text of the figure, placed above all subplots.
+  pub fn suptitle(mut self, text: &str) -> Self {
+    self.suptitle = Some(text.to_owned());
+    self
+  }
+
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
+    mpl.figure(&self.figsize, &self.dpi, &self.facecolor, &self.edgecolor)?;
+    if let Some(ref suptitle) = self.suptitle {
+      mpl.suptitle(suptitle)?;
+    }
     if let Some(ref subplots) = self.subplots {
       subplots.apply(mpl)?;
     }
@@ -35,8 +73,8 @@ pub struct Subplots<'a> {
 impl<'a> Subplots<'a> {
   pub fn new(rows: u32, cols: u32) -> Self {
     Subplots {
-      rows: rows,
-      cols: cols,
+      rows,
+      cols,
       share_x: false,
       share_y: false,
       axes: (0..((rows * cols) as usize)).map(|_| None).collect(),
@@ -58,9 +96,9 @@ impl<'a> Subplots<'a> {
     self
   }
 
-  pub fn apply<B: Backend>(&self, mpl: &mut B) -> io::Result<()> {
+  pub fn apply<B: Backend>(&self, mpl: &mut B) -> Result<()> {
     for (i, axes) in self.axes.iter().enumerate() {
-      if let &Some(ref axes) = axes {
+      if let Some(ref axes) = *axes {
         mpl.subplot(self.rows, self.cols, (i + 1) as u32)?;
         axes.apply(mpl)?;
       }
@@ -68,3 +106,60 @@ impl<'a> Subplots<'a> {
     Ok(())
   }
 }
+
+
+#[cfg(test)]
+mod tests {
+  use super::{Figure, Subplots};
+  use axes2d::Axes2D;
+  use backend::{Call, Recorder};
+
+  #[test]
+  fn apply_figure_settings() {
+    let mut rec = Recorder::new();
+    Figure::default()
+      .figsize(8.0, 6.0)
+      .dpi(150.0)
+      .facecolor("white")
+      .suptitle("results")
+      .apply(&mut rec)
+      .unwrap();
+
+    assert_eq!(rec.calls(),
+               [Call::Figure {
+                  figsize: Some((8.0, 6.0)),
+                  dpi: Some(150.0),
+                  facecolor: Some("white".to_owned()),
+                  edgecolor: None,
+                },
+                Call::Suptitle("results".to_owned())]);
+  }
+
+  #[test]
+  fn apply_empty_figure() {
+    let mut rec = Recorder::new();
+    Figure::default().apply(&mut rec).unwrap();
+    assert_eq!(rec.methods(), ["figure"]);
+  }
+
+  #[test]
+  fn subplots_skip_empty_axes() {
+    let mut rec = Recorder::new();
+    Figure::default()
+      .subplots(Subplots::new(2, 2)
+                  .at(0, Axes2D::default().xlabel("first"))
+                  .at(3, Axes2D::default().xlabel("last")))
+      .apply(&mut rec)
+      .unwrap();
+
+    let subplots: Vec<_> = rec.calls()
+      .iter()
+      .filter(|call| call.method() == "subplot")
+      .cloned()
+      .collect();
+    assert_eq!(subplots,
+               [Call::Subplot { rows: 2, cols: 2, n: 1 }, Call::Subplot { rows: 2, cols: 2, n: 4 }]);
+    assert_eq!(rec.methods(),
+               ["figure", "subplot", "xlabel", "grid", "subplot", "xlabel", "grid"]);
+  }
+}
@@ -1,11 +1,18 @@
 #[cfg(feature = "native")]
 extern crate cpython;
+#[cfg(unix)]
+extern crate libc;
 
 mod axes2d;
+mod error;
 mod figure;
+mod savefig;
 
 // re-exports
 pub mod backend;
-pub use axes2d::{Axes2D, PlotData, Scatter, Line2D, FillBetween};
+pub use axes2d::{Axes2D, Colorbar, Scale, NonFinite, PlotData, Scatter, Line2D, FillBetween, Bar, Bins, Hist, Deviation, ErrorBar,
+                 Image, Levels, Contour, ContourFilled};
 pub use backend::Backend;
+pub use error::{Error, Result};
 pub use figure::{Figure, Subplots};
+pub use savefig::{Format, SaveOptions};
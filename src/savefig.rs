@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+/// Output format of a saved figure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+  Png,
+  Svg,
+  Pdf,
+  Eps,
+}
+
+impl Format {
+  /// the name of format, which is also used as the file extension.
+  pub fn as_str(&self) -> &'static str {
+    match *self {
+      Format::Png => "png",
+      Format::Svg => "svg",
+      Format::Pdf => "pdf",
+      Format::Eps => "eps",
+    }
+  }
+}
+
+/// Options of `savefig()`.
+///
+/// the default values are same as matplotlib's ones. the fields are read by the backends.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SaveOptions {
+  pub(crate) dpi: Option<f64>,
+  pub(crate) format: Option<Format>,
+  pub(crate) transparent: bool,
+  pub(crate) tight: bool,
+  pub(crate) pad_inches: Option<f64>,
+  pub(crate) metadata: BTreeMap<String, String>,
+}
+
+impl SaveOptions {
+  /// set the resolution of output, in dots per inch.
+  pub fn dpi(mut self, dpi: f64) -> Self {
+    self.dpi = Some(dpi);
+    self
+  }
+
+  /// set the output format.
+  ///
+  /// if not set, the format is inferred from the extension of filename.
+  pub fn format(mut self, format: Format) -> Self {
+    self.format = Some(format);
+    self
+  }
+
+  /// set whether the background of the figure and axes is transparent.
+  pub fn transparent(mut self, transparent: bool) -> Self {
+    self.transparent = transparent;
+    self
+  }
+
+  /// set whether the bounding box is shrunk to the drawn area (`bbox_inches="tight"`).
+  pub fn tight(mut self, tight: bool) -> Self {
+    self.tight = tight;
+    self
+  }
+
+  /// set the padding around the figure when `tight` is enabled, in inches.
+  pub fn pad_inches(mut self, pad: f64) -> Self {
+    self.pad_inches = Some(pad);
+    self
+  }
+
+  /// add an entry of metadata stored in the output file (e.g. `"Title"`, `"Author"`).
+  pub fn metadata(mut self, key: &str, value: &str) -> Self {
+    self.metadata.insert(key.to_owned(), value.to_owned());
+    self
+  }
+}